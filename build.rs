@@ -0,0 +1,234 @@
+//! Build script for the optional font features
+//!
+//! With the `subset` feature enabled, the font for each enabled style is cut down
+//! to the icons named in the `MATERIAL_DESIGN_ICONS_SUBSET` environment variable
+//!
+//! With the `filled`, `bold` or `filled-bold` features enabled, a static instance of the font
+//! for each enabled style is baked into `OUT_DIR`, with its own family name
+//!
+//! With the `compressed` feature enabled, the font for each enabled style is
+//! brotli-compressed into `OUT_DIR`, to be embedded in place of the raw TTF  
+//! Subset fonts and baked instances are also written raw, for the `ICON_FONT` constants that read them from `OUT_DIR`
+//!
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(icons_subset)");
+
+    #[cfg(any(
+        feature = "compressed",
+        feature = "subset",
+        feature = "filled",
+        feature = "bold",
+        feature = "filled-bold"
+    ))]
+    fonts::process_fonts();
+}
+
+/// Writes the fonts that the enabled features change into `OUT_DIR`
+#[cfg(any(
+    feature = "compressed",
+    feature = "subset",
+    feature = "filled",
+    feature = "bold",
+    feature = "filled-bold"
+))]
+mod fonts {
+    /// The feature, font file stem and family name for each style
+    const FONTS: &[(&str, &str, &str)] = &[
+        (
+            "outlined",
+            "MaterialSymbolsOutlined",
+            "Material Symbols Outlined",
+        ),
+        (
+            "rounded",
+            "MaterialSymbolsRounded",
+            "Material Symbols Rounded",
+        ),
+        ("sharp", "MaterialSymbolsSharp", "Material Symbols Sharp"),
+    ];
+
+    /// Bake, subset and/or compress the font for every enabled style into `OUT_DIR`
+    pub fn process_fonts() {
+        let out_dir =
+            std::path::PathBuf::from(std::env::var("OUT_DIR").expect("OUT_DIR is not set"));
+
+        // Tells the crate, and its tests, that the embedded fonts only have some of the icons
+        #[cfg(feature = "subset")]
+        if crate::subsetting::requested_icons().is_some() {
+            println!("cargo:rustc-cfg=icons_subset");
+        }
+
+        for (feature, stem, _family) in FONTS {
+            if !feature_enabled(feature) {
+                continue;
+            }
+
+            let path = format!("src/fonts/{stem}.ttf");
+            println!("cargo:rerun-if-changed={path}");
+
+            let data = std::fs::read(&path).expect("Could not read font file");
+
+            #[cfg(any(feature = "filled", feature = "bold", feature = "filled-bold"))]
+            for (instance, suffix, axes) in crate::instances::INSTANCES {
+                if feature_enabled(instance) {
+                    let family_name = format!("{_family} {suffix}");
+                    let data = crate::instances::bake_instance(&data, axes, &family_name);
+                    write_font(&out_dir, &format!("{stem}-{instance}.ttf"), data, true);
+                }
+            }
+
+            write_font(&out_dir, &format!("{stem}.ttf"), data, false);
+        }
+    }
+
+    /// Subset and/or compress a font, then write it to `OUT_DIR`  
+    /// The raw font is written if it was subset, or if `raw` is set - otherwise `ICON_FONT` embeds
+    /// the original from `src/fonts`, and only the compressed font is needed
+    fn write_font(out_dir: &std::path::Path, file: &str, data: Vec<u8>, raw: bool) {
+        #[cfg(feature = "subset")]
+        let (data, raw) = match crate::subsetting::requested_icons() {
+            Some(icons) => (crate::subsetting::subset_font(&data, &icons, file), true),
+            None => (data, raw),
+        };
+
+        #[cfg(feature = "compressed")]
+        std::fs::write(
+            out_dir.join(format!("{file}.br")),
+            crate::compress::compress_font(&data),
+        )
+        .expect("Could not write font file");
+
+        if raw {
+            std::fs::write(out_dir.join(file), data).expect("Could not write font file");
+        }
+    }
+
+    /// Check if a crate feature is enabled, by its name in `Cargo.toml`
+    fn feature_enabled(feature: &str) -> bool {
+        let feature = feature.to_uppercase().replace('-', "_");
+        std::env::var_os(format!("CARGO_FEATURE_{feature}")).is_some()
+    }
+}
+
+/// The instancing code is shared with the `parser` feature
+#[cfg(any(
+    feature = "subset",
+    feature = "filled",
+    feature = "bold",
+    feature = "filled-bold"
+))]
+#[allow(dead_code)] // With only the `subset` feature, just `with_tables` is used
+#[path = "src/font/instance.rs"]
+mod instance;
+
+/// The subsetting code is shared with the `parser` feature
+#[cfg(feature = "subset")]
+#[path = "src/font/subset.rs"]
+mod subset;
+
+/// The icon list reading is shared with the crate's tests
+#[cfg(feature = "subset")]
+#[path = "src/font/subset_list.rs"]
+mod subset_list;
+
+#[cfg(any(feature = "filled", feature = "bold", feature = "filled-bold"))]
+mod instances {
+    use crate::instance::instance_font;
+    use allsorts::{binary::read::ReadScope, font_data::FontData};
+
+    /// Axis tags and the values to bake them at
+    type Axes = &'static [(&'static [u8; 4], f32)];
+
+    /// The feature and family name suffix of each pre-baked instance, and the axis values it is baked at  
+    /// These must match the names given by `IconVariation::instance_family_name`
+    pub const INSTANCES: &[(&str, &str, Axes)] = &[
+        ("filled", "Filled 400", &[(b"FILL", 1.0)]),
+        ("bold", "700", &[(b"wght", 700.0)]),
+        (
+            "filled-bold",
+            "Filled 700",
+            &[(b"FILL", 1.0), (b"wght", 700.0)],
+        ),
+    ];
+
+    /// Bake a static instance of a variable font, with its own family name
+    pub fn bake_instance(data: &[u8], axes: Axes, family_name: &str) -> Vec<u8> {
+        println!("cargo:rerun-if-changed=src/font/instance.rs");
+
+        let font_data = ReadScope::new(data)
+            .read::<FontData<'_>>()
+            .expect("Could not parse font file");
+        let provider = font_data
+            .table_provider(0)
+            .expect("Could not parse font tables");
+
+        let axes = axes
+            .iter()
+            .map(|(tag, value)| (u32::from_be_bytes(**tag), *value))
+            .collect::<Vec<_>>();
+        instance_font(&provider, &axes, family_name).expect("Could not bake font instance")
+    }
+}
+
+#[cfg(feature = "compressed")]
+mod compress {
+    /// Brotli-compress a font
+    pub fn compress_font(data: &[u8]) -> Vec<u8> {
+        let mut compressed = Vec::with_capacity(data.len());
+        let params = brotli::enc::BrotliEncoderParams {
+            quality: 9,
+            lgwin: 24,
+            ..Default::default()
+        };
+        brotli::BrotliCompress(&mut &data[..], &mut compressed, &params)
+            .expect("Could not compress font file");
+        compressed
+    }
+}
+
+#[cfg(feature = "subset")]
+mod subsetting {
+    use crate::subset::subset_font as subset_glyphs;
+    use crate::subset_list::{icon_glyphs, parse_icon_list, SubsetListError};
+    use allsorts::{binary::read::ReadScope, font::Font, font_data::FontData};
+
+    /// Environment variable listing the icons to keep, separated by commas or whitespace
+    const SUBSET_VAR: &str = "MATERIAL_DESIGN_ICONS_SUBSET";
+
+    /// Return the names of the requested icons, or None to keep the whole font
+    pub fn requested_icons() -> Option<Vec<String>> {
+        println!("cargo:rerun-if-env-changed={SUBSET_VAR}");
+        let icons = std::env::var(SUBSET_VAR).ok()?;
+        let Ok(icons) = parse_icon_list(&icons) else {
+            panic!("{SUBSET_VAR} does not name any icons - list the icons to keep, or unset it to embed the whole fonts");
+        };
+        Some(icons)
+    }
+
+    /// Cut a font down to the named icons  
+    /// The icons keep their original codepoints and ligatures, so the generated enum still matches the font
+    pub fn subset_font(data: &[u8], icons: &[String], file: &str) -> Vec<u8> {
+        println!("cargo:rerun-if-changed=src/font/subset.rs");
+        println!("cargo:rerun-if-changed=src/font/subset_list.rs");
+        println!("cargo:rerun-if-changed=src/font/instance.rs");
+
+        let font_data = ReadScope::new(data)
+            .read::<FontData<'_>>()
+            .expect("Could not parse font file");
+        let provider = font_data
+            .table_provider(0)
+            .expect("Could not parse font tables");
+        let font = Font::new(provider).expect("Could not load font");
+
+        let glyph_ids = match icon_glyphs(&font, icons) {
+            Ok(glyph_ids) => glyph_ids,
+            Err(SubsetListError::NotAnIcon(icon)) => {
+                panic!("{SUBSET_VAR} contains `{icon}`, which is not an icon in {file}")
+            }
+            Err(err) => panic!("Could not read the icons in {file}: {err}"),
+        };
+
+        subset_glyphs(&font.font_table_provider, &glyph_ids).expect("Could not subset font")
+    }
+}
@@ -1,38 +1,233 @@
 //! Regenerates `src/glyphs.rs` with the icon data from Google's Material Design Icons font
 //!
-//! This script will load the outlined, rounded, and sharp fonts
+//! This script will load the outlined, rounded, and sharp fonts,
+//! and check that they all contain the same icons
 //!
-use material_design_icons::font::{Font, FontError, FontMapper, Glyph};
+//! Names that the previous release of the crate had, but the fonts no longer use,
+//! are kept as deprecated constants pointing to the icon at the same codepoint
+//!
+//! Run with `instance` to bake a static instance of one of the fonts instead - see [`INSTANCE_USAGE`]
+//!
+use material_design_icons::{
+    font::{Font, FontError, FontMapper, Glyph},
+    IconName, IconVariation, Style,
+};
 
 const TARGET: &str = "src/glyphs.rs";
 
-fn main() -> Result<(), FontError> {
+const INSTANCE_USAGE: &str = "Usage: parse instance <outlined|rounded|sharp> [--fill <value>] [--weight <value>] [--grade <value>] [--optical-size <value>] [--name <family name>] [--output <file>]";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if let Some(("instance", args)) = args.split_first().map(|(cmd, rest)| (cmd.as_str(), rest)) {
+        return bake_instance(args);
+    }
+
     print!("Loading outlined font... ");
     let outlined = Font::new_outlined()?;
-    print!("Generating code... ");
-    let outlined = codegen_font("Outlined", &outlined)?;
+    print!("Reading glyphs... ");
+    let outlined = FontGlyphs::new("Outlined", &outlined)?;
     println!("Done!");
 
     print!("Loading rounded font... ");
     let rounded = Font::new_rounded()?;
-    print!("Generating code... ");
-    let rounded = codegen_font("Rounded", &rounded)?;
+    print!("Reading glyphs... ");
+    let rounded = FontGlyphs::new("Rounded", &rounded)?;
     println!("Done!");
 
     print!("Loading sharp font... ");
     let sharp = Font::new_sharp()?;
-    print!("Generating code... ");
-    let sharp = codegen_font("Sharp", &sharp)?;
+    print!("Reading glyphs... ");
+    let sharp = FontGlyphs::new("Sharp", &sharp)?;
+    println!("Done!");
+
+    print!("Comparing styles... ");
+    let glyphs = shared_glyphs(vec![outlined, rounded, sharp])?;
+    println!("Done!");
+
+    print!("Checking for renamed icons... ");
+    let renamed = renamed_glyphs(&glyphs);
     println!("Done!");
 
+    print!("Generating code... ");
+    let code = codegen_font("IconName", &glyphs, &renamed);
     print!("Finalizing code... ");
-    let code = codegen_file(&[outlined, rounded, sharp]);
+    let code = codegen_file(&[code]);
     print!("Writing to file... ");
     std::fs::write(TARGET, code)?;
     println!("Done!");
     Ok(())
 }
 
+/// Write a static instance of one of the fonts, with its own family name  
+/// The family name defaults to one like "Material Symbols Sharp Filled 700", and the file name to the family name
+fn bake_instance(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = args.iter();
+    let style = match args.next().map(String::as_str) {
+        Some("outlined") => Style::Outlined,
+        Some("rounded") => Style::Rounded,
+        Some("sharp") => Style::Sharp,
+        _ => return Err(INSTANCE_USAGE.into()),
+    };
+
+    let mut variation = IconVariation::DEFAULT;
+    let mut family_name = None;
+    let mut output = None;
+    while let Some(option) = args.next() {
+        let Some(value) = args.next() else {
+            return Err(format!("Missing value for `{option}`\n{INSTANCE_USAGE}").into());
+        };
+
+        match option.as_str() {
+            "--fill" => variation.fill = value.parse()?,
+            "--weight" => variation.weight = value.parse()?,
+            "--grade" => variation.grade = value.parse()?,
+            "--optical-size" => variation.optical_size = value.parse()?,
+            "--name" => family_name = Some(value.clone()),
+            "--output" => output = Some(value.clone()),
+            _ => return Err(format!("Unknown option `{option}`\n{INSTANCE_USAGE}").into()),
+        }
+    }
+
+    let family_name =
+        family_name.unwrap_or_else(|| variation.instance_family_name(style.family_name()));
+    let output = output.unwrap_or_else(|| format!("{}.ttf", family_name.replace(' ', "")));
+
+    print!("Baking {family_name}... ");
+    let font = Font::new(style.icon_font())?;
+    let data = font.instance(&variation, &family_name)?;
+    print!("Writing to file... ");
+    std::fs::write(&output, data)?;
+    println!("Done! Saved as {output}");
+    Ok(())
+}
+
+/// The glyphs of a single font, split into icons and support glyphs
+pub struct FontGlyphs<'a> {
+    pub style: &'static str,
+    pub icons: Vec<Glyph<'a>>,
+    pub support: Vec<Glyph<'a>>,
+    pub aliases: Vec<Alias>,
+}
+
+/// A name that resolves to an icon without being its glyph name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alias {
+    pub name: String,
+    pub codepoint: u32,
+}
+impl<'a> FontGlyphs<'a> {
+    /// Read and classify all the glyphs in a font
+    pub fn new(style: &'static str, font: &'a Font<'a>) -> Result<Self, FontError> {
+        let mapper = FontMapper::new(font)?;
+        let mut glyphs = mapper.all_chars()?;
+
+        // Sort by name so the output is stable between runs
+        glyphs.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let icons: Vec<_> = glyphs
+            .into_iter()
+            .filter(|glyph| glyph.class() == GlyphClass::Icon)
+            .collect();
+
+        // Letters are mapped to both cases, so support glyphs are listed once for every codepoint
+        let mut support: Vec<_> = mapper
+            .all_codepoints()?
+            .into_iter()
+            .filter(|glyph| glyph.class() == GlyphClass::Support)
+            .collect();
+        support.sort_by(|a, b| (&a.name, a.codepoint).cmp(&(&b.name, b.codepoint)));
+
+        // Ligatures that spell out an icon under a name other than its own
+        let aliases = mapper
+            .ligatures()?
+            .into_iter()
+            .filter(|ligature| ligature.is_alias())
+            .filter(|ligature| icons.iter().any(|icon| icon.id == ligature.glyph.id))
+            .map(|ligature| Alias {
+                name: ligature.name,
+                codepoint: ligature.glyph.codepoint,
+            })
+            .collect();
+
+        Ok(Self {
+            style,
+            icons,
+            support,
+            aliases,
+        })
+    }
+
+    /// Find the icon at a codepoint
+    fn icon_at(&self, codepoint: u32) -> Option<&Glyph<'a>> {
+        self.icons.iter().find(|icon| icon.codepoint == codepoint)
+    }
+
+    /// Check if a name is used by an icon or an alias
+    fn has_name(&self, name: &str) -> bool {
+        self.icons.iter().any(|icon| icon.name == name)
+            || self.aliases.iter().any(|alias| alias.name == name)
+    }
+
+    /// Return the names and codepoints of a set of glyphs, for comparison
+    fn entries<'b>(glyphs: &'b [Glyph<'_>]) -> Vec<(&'b str, u32)> {
+        glyphs
+            .iter()
+            .map(|glyph| (glyph.name.as_ref(), glyph.codepoint))
+            .collect()
+    }
+}
+
+/// Make sure every style contains the same glyphs at the same codepoints  
+/// The generated code shares a single icon enum between all the styles, so they must match exactly
+fn shared_glyphs(mut fonts: Vec<FontGlyphs<'_>>) -> Result<FontGlyphs<'_>, String> {
+    let first = fonts.remove(0);
+    for font in &fonts {
+        let icons_match = FontGlyphs::entries(&font.icons) == FontGlyphs::entries(&first.icons);
+        let support_match =
+            FontGlyphs::entries(&font.support) == FontGlyphs::entries(&first.support);
+
+        let aliases_match = font.aliases == first.aliases;
+
+        if !icons_match || !support_match || !aliases_match {
+            return Err(format!(
+                "The {} and {} fonts do not contain the same glyphs",
+                first.style, font.style
+            ));
+        }
+    }
+
+    Ok(first)
+}
+
+/// Find the names from the previous release of the crate that the fonts no longer use  
+/// Each is kept as an alias of the icon now at its codepoint - icons that were removed outright are reported and dropped
+fn renamed_glyphs(glyphs: &FontGlyphs<'_>) -> Vec<Alias> {
+    let previous = IconName::iter()
+        .map(|icon| (icon.name(), icon))
+        .chain(IconName::ALIASES.iter().copied())
+        .chain(IconName::RENAMED.iter().copied());
+
+    let mut renamed = Vec::new();
+    for (name, icon) in previous {
+        let codepoint = icon as u32;
+        if glyphs.has_name(name) || renamed.iter().any(|alias: &Alias| alias.name == name) {
+            continue;
+        }
+
+        match glyphs.icon_at(codepoint) {
+            Some(_) => renamed.push(Alias {
+                name: name.to_string(),
+                codepoint,
+            }),
+            None => print!("`{name}` was removed... "),
+        }
+    }
+
+    renamed.sort_by(|a, b| a.name.cmp(&b.name));
+    renamed
+}
+
 /// Generate the code for the entire file
 pub fn codegen_file(fonts: &[String]) -> String {
     const HEADERS: &[&str] = &[
@@ -47,22 +242,67 @@ pub fn codegen_file(fonts: &[String]) -> String {
 }
 
 /// Generate the code for a font
-fn codegen_font(name: &str, font: &Font<'_>) -> Result<String, FontError> {
+fn codegen_font(name: &str, glyphs: &FontGlyphs<'_>, renamed: &[Alias]) -> String {
+    // Skip any alias that would clash with an existing identifier
+    let mut identifiers = glyphs
+        .icons
+        .iter()
+        .map(|glyph| glyph.identifier())
+        .collect::<std::collections::HashSet<_>>();
+    let mut unique = |aliases: &[Alias]| -> Vec<AliasEntry> {
+        aliases
+            .iter()
+            .filter_map(|alias| {
+                let identifier = identifier(&alias.name);
+                let target = glyphs.icon_at(alias.codepoint)?;
+                if !identifiers.insert(identifier.clone()) {
+                    print!(
+                        "Skipping `{}`, which clashes with another name... ",
+                        alias.name
+                    );
+                    return None;
+                }
+
+                Some(AliasEntry {
+                    name: alias.name.clone(),
+                    identifier,
+                    target: target.identifier(),
+                    target_name: target.name.to_string(),
+                })
+            })
+            .collect()
+    };
+    let aliases = unique(&glyphs.aliases);
+    let renamed = unique(renamed);
+
+    [
+        codegen_enum(name, &glyphs.icons),
+        codegen_all(name, &glyphs.icons),
+        codegen_names(name, &glyphs.icons),
+        codegen_codepoints(name, &glyphs.icons),
+        codegen_alias_consts(name, &aliases, &renamed),
+        codegen_alias_tables(name, &aliases, &renamed),
+        codegen_identifiers(&glyphs.icons, &aliases, &renamed),
+        codegen_support(&glyphs.support),
+    ]
+    .join("\n\n")
+}
+
+/// Generate the icon enum for a font
+fn codegen_enum(name: &str, glyphs: &[Glyph<'_>]) -> String {
     const PREAMBLE: &[&str] = &[
         "/// List of Google's Material Design icon names and associated codepoints  ",
+        "/// The names and codepoints are the same in every style of the font  ",
         "/// Go to [https://fonts.google.com/icons] to see the full list of icons",
-        "#[derive(Debug, Clone, Copy)]",
+        "#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]",
         "#[repr(u32)]",
     ];
 
     let preamble = PREAMBLE.join("\n");
     let name = format!("pub enum {name} {{");
 
-    let mapper = FontMapper::new(font)?;
-    let glyphs = mapper.all_chars()?;
-
     let entries = glyphs
-        .into_iter()
+        .iter()
         .map(|glyph| {
             let comment = format!("    /// [Preview `{}`]({})", glyph.name, glyph.query_url());
             let code = format!("    {} = 0x{:0x},", glyph.identifier(), glyph.codepoint);
@@ -73,7 +313,298 @@ fn codegen_font(name: &str, font: &Font<'_>) -> Result<String, FontError> {
         .join("\n\n");
 
     let suffix = "}".to_string();
-    Ok([preamble, name, entries, suffix].join("\n"))
+    [preamble, name, entries, suffix].join("\n")
+}
+
+/// Generate the list of all icons in a font, in the same order as the enum
+fn codegen_all(name: &str, glyphs: &[Glyph<'_>]) -> String {
+    let prefix = [
+        format!("impl {name} {{"),
+        "    /// Every icon in the font, in declaration order".to_string(),
+        "    pub const ALL: &'static [Self] = &[".to_string(),
+    ]
+    .join("\n");
+
+    let entries = glyphs
+        .iter()
+        .map(|glyph| format!("        Self::{},", glyph.identifier()))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let suffix = ["    ];", "}"].join("\n");
+    [prefix, entries, suffix].join("\n")
+}
+
+/// Generate the name lookup table for a font
+/// Expects the glyphs to already be sorted by name
+fn codegen_names(name: &str, glyphs: &[Glyph<'_>]) -> String {
+    let prefix = [
+        format!("impl {name} {{"),
+        "    /// Glyph names and their icons, sorted by name for binary search".to_string(),
+        "    pub(crate) const NAMES: &'static [(&'static str, Self)] = &[".to_string(),
+    ]
+    .join("\n");
+
+    let entries = glyphs
+        .iter()
+        .map(|glyph| {
+            table_entry(&[
+                format!("{:?}", glyph.name),
+                format!("Self::{}", glyph.identifier()),
+            ])
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let suffix = ["    ];", "}"].join("\n");
+    [prefix, entries, suffix].join("\n")
+}
+
+/// Generate the codepoint lookup table for a font
+fn codegen_codepoints(name: &str, glyphs: &[Glyph<'_>]) -> String {
+    let prefix = [
+        format!("impl {name} {{"),
+        "    /// Icons and their glyph names, sorted by codepoint for binary search".to_string(),
+        "    pub(crate) const CODEPOINTS: &'static [(Self, &'static str)] = &[".to_string(),
+    ]
+    .join("\n");
+
+    let mut glyphs = glyphs.iter().collect::<Vec<_>>();
+    glyphs.sort_by_key(|glyph| glyph.codepoint);
+
+    let entries = glyphs
+        .iter()
+        .map(|glyph| {
+            table_entry(&[
+                format!("Self::{}", glyph.identifier()),
+                format!("{:?}", glyph.name),
+            ])
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let suffix = ["    ];", "}"].join("\n");
+    [prefix, entries, suffix].join("\n")
+}
+
+/// An alias ready for codegen, with the identifiers of the constant and of the icon it points to
+struct AliasEntry {
+    name: String,
+    identifier: String,
+    target: String,
+    target_name: String,
+}
+
+/// Generate the alias constants for a font  
+/// Names from earlier releases are deprecated, pointing to the icon's new name
+fn codegen_alias_consts(name: &str, aliases: &[AliasEntry], renamed: &[AliasEntry]) -> String {
+    let prefix = [
+        "#[allow(non_upper_case_globals)]".to_string(),
+        format!("impl {name} {{"),
+    ]
+    .join("\n");
+
+    let constant = |identifier: &str, target: &str| {
+        let line = format!("    pub const {identifier}: Self = Self::{target};");
+        if line.len() <= 100 {
+            return line;
+        }
+        format!("    pub const {identifier}: Self =\n        Self::{target};")
+    };
+
+    let aliases = aliases.iter().map(|alias| {
+        let comment = format!(
+            "    /// Alias of [`{name}::{}`], spelled out by the `{}` ligature",
+            alias.target, alias.name
+        );
+        [comment, constant(&alias.identifier, &alias.target)].join("\n")
+    });
+
+    let renamed = renamed.iter().map(|alias| {
+        let comment = format!("    /// Former name of [`{name}::{}`]", alias.target);
+        let attribute = format!(
+            "    #[deprecated(note = \"renamed to `{}`\")]",
+            alias.target_name
+        );
+        [
+            comment,
+            attribute,
+            constant(&alias.identifier, &alias.target),
+        ]
+        .join("\n")
+    });
+
+    let entries = aliases.chain(renamed).collect::<Vec<_>>().join("\n\n");
+    let suffix = "}".to_string();
+    [prefix, entries, suffix].join("\n")
+}
+
+/// Generate the alias and renamed icon lookup tables for a font
+fn codegen_alias_tables(name: &str, aliases: &[AliasEntry], renamed: &[AliasEntry]) -> String {
+    let table = |doc: &[&str], table: &str, entries: &[AliasEntry]| {
+        let mut lines = doc
+            .iter()
+            .map(|line| format!("    /// {line}"))
+            .collect::<Vec<_>>();
+        let declaration = format!("    pub const {table}: &'static [(&'static str, Self)] = &[");
+        if entries.is_empty() {
+            lines.push(declaration + "];");
+            return lines.join("\n");
+        }
+
+        lines.push(declaration);
+        for alias in entries {
+            lines.push(table_entry(&[
+                format!("{:?}", alias.name),
+                format!("Self::{}", alias.target),
+            ]));
+        }
+        lines.push("    ];".to_string());
+        lines.join("\n")
+    };
+
+    [
+        format!("impl {name} {{"),
+        table(
+            &[
+                "Alias names and their icons, sorted by name for binary search  ",
+                "Aliases are ligatures that spell out an icon under another name",
+            ],
+            "ALIASES",
+            aliases,
+        ),
+        String::new(),
+        table(
+            &[
+                "Names from earlier releases of the font and the icons they now belong to,",
+                "sorted by name for binary search",
+            ],
+            "RENAMED",
+            renamed,
+        ),
+        "}".to_string(),
+    ]
+    .join("\n")
+}
+
+/// Generate a macro passing the identifier of every icon, alias and renamed icon to another macro  
+/// Lets each style module declare a constant for every icon without repeating the list
+fn codegen_identifiers(
+    glyphs: &[Glyph<'_>],
+    aliases: &[AliasEntry],
+    renamed: &[AliasEntry],
+) -> String {
+    let list = |identifiers: Vec<String>| {
+        if identifiers.is_empty() {
+            return "[]".to_string();
+        }
+        let entries = identifiers
+            .iter()
+            .map(|identifier| format!("                {identifier},"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        ["[".to_string(), entries, "            ]".to_string()].join("\n")
+    };
+
+    let icons = list(glyphs.iter().map(|glyph| glyph.identifier()).collect());
+    let aliases = list(
+        aliases
+            .iter()
+            .map(|alias| alias.identifier.clone())
+            .collect(),
+    );
+    let renamed = list(
+        renamed
+            .iter()
+            .map(|alias| {
+                let note = format!("renamed to `{}`", alias.target_name);
+                format!("{} => {note:?}", alias.identifier)
+            })
+            .collect(),
+    );
+
+    [
+        "/// Calls `$callback` with the identifiers of every icon, alias and renamed icon  "
+            .to_string(),
+        "/// Renamed icons are followed by the note they are deprecated with".to_string(),
+        "macro_rules! icon_identifiers {".to_string(),
+        "    ($callback:ident) => {".to_string(),
+        "        $callback! {".to_string(),
+        format!("            icons: {icons},"),
+        format!("            aliases: {aliases},"),
+        format!("            renamed: {renamed},"),
+        "        }".to_string(),
+        "    };".to_string(),
+        "}".to_string(),
+    ]
+    .join("\n")
+}
+
+/// Generate the table of support glyphs shared by the fonts  
+/// A glyph mapped to several codepoints has one entry for each
+fn codegen_support(glyphs: &[Glyph<'_>]) -> String {
+    let prefix = [
+        "/// Glyph names and codepoints of the non-icon glyphs in the fonts  ",
+        "/// These are the letters, digits and punctuation used to spell out icon ligatures,",
+        "/// and are not icons themselves. Glyphs mapped to several codepoints, such as the upper",
+        "/// and lowercase forms of a letter, are listed once for each codepoint",
+        "pub const SUPPORT_GLYPHS: &[(&str, u32)] = &[",
+    ]
+    .join("\n");
+
+    let entries = glyphs
+        .iter()
+        .map(|glyph| format!("    ({:?}, 0x{:0x}),", glyph.name, glyph.codepoint))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let suffix = "];".to_string();
+    [prefix, entries, suffix].join("\n")
+}
+
+/// Format a tuple entry in a generated table, wrapping it the same way rustfmt would
+fn table_entry(fields: &[String]) -> String {
+    const MAX_WIDTH: usize = 60;
+
+    let line = fields.join(", ");
+    if line.len() <= MAX_WIDTH {
+        return format!("        ({line}),");
+    }
+
+    let fields = fields.join(",\n            ");
+    format!("        (\n            {fields},\n        ),")
+}
+
+/// How a glyph from the font should be exposed by the generated code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlyphClass {
+    /// A real icon, found in the private use area
+    Icon,
+
+    /// A glyph used by the ligature machinery, such as a letter or digit
+    Support,
+
+    /// A glyph that is not a real character, such as `.notdef`
+    Skip,
+}
+
+pub trait ClassifyExt {
+    /// Decide whether the glyph is an icon or a support glyph
+    fn class(&self) -> GlyphClass;
+}
+impl ClassifyExt for Glyph<'_> {
+    fn class(&self) -> GlyphClass {
+        const PRIVATE_USE: &[std::ops::RangeInclusive<u32>] =
+            &[0xE000..=0xF8FF, 0xF0000..=0xFFFFD, 0x100000..=0x10FFFD];
+
+        if self.name.starts_with('.') || self.char().is_none_or(|c| c == '\u{FFFF}') {
+            GlyphClass::Skip
+        } else if PRIVATE_USE.iter().any(|r| r.contains(&self.codepoint)) {
+            GlyphClass::Icon
+        } else {
+            GlyphClass::Support
+        }
+    }
 }
 
 pub trait QueryExt {
@@ -92,37 +623,80 @@ impl QueryExt for Glyph<'_> {
     }
 
     fn identifier(&self) -> String {
-        let mut identifier = self.name.to_lowercase();
-        identifier = identifier.replace('.', "");
-
-        // First, replace all `_[a-z0-9]` with the uppercase variant
-        let mut chars = identifier.chars();
-        let mut result = String::new();
-        while let Some(c) = chars.next() {
-            if c == '_' {
-                if let Some(next) = chars.next() {
-                    result.push(next.to_uppercase().next().unwrap());
-                }
-            } else {
-                result.push(c);
+        identifier(&self.name)
+    }
+}
+
+/// Convert a glyph name to a SnakeCase identifier
+fn identifier(name: &str) -> String {
+    let mut identifier = name.to_lowercase();
+    identifier = identifier.replace('.', "");
+
+    // First, replace all `_[a-z0-9]` with the uppercase variant
+    let mut chars = identifier.chars();
+    let mut result = String::new();
+    while let Some(c) = chars.next() {
+        if c == '_' {
+            if let Some(next) = chars.next() {
+                result.push(next.to_uppercase().next().unwrap());
             }
+        } else {
+            result.push(c);
         }
-        identifier = result;
+    }
+    identifier = result;
 
-        // If we start with a number, add a leading underscore
-        if identifier.chars().next().unwrap().is_numeric() {
-            identifier = format!("_{}", identifier);
-        }
+    // If we start with a number, add a leading underscore
+    if identifier.chars().next().unwrap().is_numeric() {
+        identifier = format!("_{}", identifier);
+    }
+
+    // Capitalize the first letter
+    identifier = identifier
+        .chars()
+        .next()
+        .unwrap()
+        .to_uppercase()
+        .collect::<String>()
+        + &identifier[1..];
+
+    identifier
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Read the glyphs of the unmodified sharp font, even if the embedded one is a subset
+    fn sharp_font() -> Font<'static> {
+        Font::from_path("src/fonts/MaterialSymbolsSharp.ttf").unwrap()
+    }
+
+    #[test]
+    fn test_renamed_glyphs() {
+        let font = sharp_font();
+        let mut glyphs = FontGlyphs::new("Sharp", &font).unwrap();
+        assert!(renamed_glyphs(&glyphs).is_empty());
+
+        // Rename one icon, and remove another outright
+        let add = glyphs.icons.iter().position(|icon| icon.name == "add");
+        glyphs.icons[add.unwrap()].name = "add_new".into();
+        glyphs.icons.retain(|icon| icon.name != "home");
 
-        // Capitalize the first letter
-        identifier = identifier
-            .chars()
-            .next()
-            .unwrap()
-            .to_uppercase()
-            .collect::<String>()
-            + &identifier[1..];
+        let renamed = renamed_glyphs(&glyphs);
+        assert_eq!(
+            renamed,
+            vec![Alias {
+                name: "add".to_string(),
+                codepoint: IconName::Add as u32,
+            }]
+        );
 
-        identifier
+        // The old name is kept as a deprecated constant, and in the lookup table
+        let code = codegen_font("IconName", &glyphs, &renamed);
+        assert!(code.contains(
+            "    /// Former name of [`IconName::AddNew`]\n    #[deprecated(note = \"renamed to `add_new`\")]\n    pub const Add: Self = Self::AddNew;"
+        ));
+        assert!(code.contains("        (\"add\", Self::AddNew),"));
     }
 }
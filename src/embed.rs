@@ -0,0 +1,145 @@
+/// Macro embedding the font for a style, either as-is or subset and compressed by the build script
+#[allow(unused_macros)] // Unused when no styles are enabled
+macro_rules! embed_font {
+    ($file:literal) => {
+        /// Raw data of the font for this style
+        ///
+        /// With the `compressed` feature, using this embeds the uncompressed font as well - use [`icon_font`] instead
+        #[cfg(not(icons_subset))]
+        pub const ICON_FONT: &[u8] = include_bytes!(concat!("fonts/", $file));
+
+        /// Raw data of the font for this style, cut down by the build script
+        /// to the icons listed in `MATERIAL_DESIGN_ICONS_SUBSET`
+        ///
+        /// With the `compressed` feature, using this embeds the uncompressed font as well - use [`icon_font`] instead
+        #[cfg(icons_subset)]
+        pub const ICON_FONT: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/", $file));
+
+        /// Brotli-compressed data of the font for this style
+        #[cfg(feature = "compressed")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "compressed")))]
+        pub const ICON_FONT_COMPRESSED: &[u8] =
+            include_bytes!(concat!(env!("OUT_DIR"), "/", $file, ".br"));
+
+        /// Return the raw data of the font for this style
+        ///
+        /// With the `compressed` feature, the font is decompressed on first use
+        pub fn icon_font() -> &'static [u8] {
+            #[cfg(feature = "compressed")]
+            {
+                static FONT: std::sync::OnceLock<Vec<u8>> = std::sync::OnceLock::new();
+                FONT.get_or_init(|| $crate::embed::decompress(ICON_FONT_COMPRESSED))
+            }
+
+            #[cfg(not(feature = "compressed"))]
+            ICON_FONT
+        }
+    };
+}
+
+/// Macro embedding the instances of a style pre-baked by the build script, one module per enabled feature  
+/// Also lists them in `INSTANCES`, for lookup by variation
+#[allow(unused_macros)] // Unused when no styles are enabled
+macro_rules! embed_instances {
+    ($stem:literal, $family:literal) => {
+        embed_instances!(@instance "filled", filled, FILLED, $stem, $family, " Filled 400");
+        embed_instances!(@instance "bold", bold, BOLD, $stem, $family, " 700");
+        embed_instances!(@instance "filled-bold", filled_bold, FILLED_BOLD, $stem, $family, " Filled 700");
+
+        /// The pre-baked instances of this style, as their variation, family name and font data
+        #[allow(dead_code)] // Empty when no instances are enabled
+        pub(crate) const INSTANCES: &[$crate::embed::Instance] = &[
+            #[cfg(feature = "filled")]
+            (filled::VARIATION, filled::FAMILY_NAME, filled::icon_font),
+            #[cfg(feature = "bold")]
+            (bold::VARIATION, bold::FAMILY_NAME, bold::icon_font),
+            #[cfg(feature = "filled-bold")]
+            (filled_bold::VARIATION, filled_bold::FAMILY_NAME, filled_bold::icon_font),
+        ];
+    };
+
+    (@instance $feature:literal, $module:ident, $variation:ident, $stem:literal, $family:literal, $suffix:literal) => {
+        #[doc = concat!("Static instance of this style, pre-baked at [`IconVariation::", stringify!($variation), "`](crate::IconVariation::", stringify!($variation), ")  ")]
+        #[doc = concat!("Load it alongside the variable font, and draw icons with the \"", $family, $suffix, "\" family")]
+        #[cfg(feature = $feature)]
+        #[cfg_attr(docsrs, doc(cfg(feature = $feature)))]
+        pub mod $module {
+            /// The variation the font was baked at
+            pub const VARIATION: $crate::IconVariation = $crate::IconVariation::$variation;
+
+            /// The family name of the baked font
+            pub const FAMILY_NAME: &str = concat!($family, $suffix);
+
+            /// Raw data of the baked font
+            ///
+            /// With the `compressed` feature, using this embeds the uncompressed font as well - use [`icon_font`] instead
+            pub const ICON_FONT: &[u8] =
+                include_bytes!(concat!(env!("OUT_DIR"), "/", $stem, "-", $feature, ".ttf"));
+
+            /// Brotli-compressed data of the baked font
+            #[cfg(feature = "compressed")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "compressed")))]
+            pub const ICON_FONT_COMPRESSED: &[u8] =
+                include_bytes!(concat!(env!("OUT_DIR"), "/", $stem, "-", $feature, ".ttf.br"));
+
+            /// Return the raw data of the baked font
+            ///
+            /// With the `compressed` feature, the font is decompressed on first use
+            pub fn icon_font() -> &'static [u8] {
+                #[cfg(feature = "compressed")]
+                {
+                    static FONT: std::sync::OnceLock<Vec<u8>> = std::sync::OnceLock::new();
+                    FONT.get_or_init(|| $crate::embed::decompress(ICON_FONT_COMPRESSED))
+                }
+
+                #[cfg(not(feature = "compressed"))]
+                ICON_FONT
+            }
+        }
+    };
+}
+
+/// A pre-baked instance of a style, as its variation, family name and font data
+pub(crate) type Instance = (crate::IconVariation, &'static str, fn() -> &'static [u8]);
+
+/// Decompress a font embedded by the build script
+#[cfg(feature = "compressed")]
+pub(crate) fn decompress(data: &[u8]) -> Vec<u8> {
+    use std::io::Read;
+
+    let mut font = Vec::new();
+    brotli_decompressor::Decompressor::new(data, 4096)
+        .read_to_end(&mut font)
+        .expect("Embedded font data is corrupt");
+    font
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    #[cfg(feature = "sharp")]
+    fn test_icon_font() {
+        let font = crate::sharp::icon_font();
+        assert_eq!(&font[..4], &[0x00, 0x01, 0x00, 0x00]);
+
+        // The font is only decompressed once
+        assert_eq!(font.as_ptr(), crate::sharp::icon_font().as_ptr());
+
+        // The uncompressed data is available whether or not the font is compressed
+        assert_eq!(crate::sharp::ICON_FONT, font);
+    }
+
+    #[test]
+    #[cfg(feature = "sharp")]
+    fn test_instance_fonts() {
+        use crate::sharp::{INSTANCES, STYLE};
+
+        for (variation, family_name, icon_font) in INSTANCES {
+            assert_eq!(&icon_font()[..4], &[0x00, 0x01, 0x00, 0x00]);
+            assert_eq!(
+                *family_name,
+                variation.instance_family_name(STYLE.family_name())
+            );
+        }
+    }
+}
@@ -5,96 +5,552 @@
 //! It also provides a few other font-introspection utilities, such as [`FontMapper`] and [`Glyph`].
 //!
 use allsorts::{
-    binary::read::{ReadBinary, ReadScope},
+    binary::read::ReadScope,
     bitmap::{BitDepth, Bitmap, BitmapGlyph, EncapsulatedFormat},
-    font::MatchingPresentation,
+    error::ParseError,
     font_data::{DynamicFontTableProvider, FontData},
-    tables::cmap::CmapSubtable,
+    glyph_info::GlyphNames,
+    tables::{
+        cmap::CmapSubtable,
+        variable_fonts::{avar::AvarTable, fvar::FvarTable, OwnedTuple},
+        FontTableProvider, HeadTable, LongHorMetric, OpenTypeData, OpenTypeFont,
+    },
+    tag,
 };
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    ops::Range,
+    path::Path,
+    sync::{Arc, Mutex, OnceLock, PoisonError},
+};
+
+use crate::{Axis, AxisRange, IconVariation, VariationError};
+
+mod instance;
+pub use instance::InstanceError;
+use instance::SubstitutionTable;
+
+mod metrics;
+pub use metrics::{BoundingBox, GlyphMetrics, VerticalMetrics};
+
+mod outline;
+pub use outline::{IconPath, PathCommand, Point};
+
+mod render;
+pub use render::{IconPixmap, PixelFormat};
+
+mod subset;
+
+/// The build script's reading of `MATERIAL_DESIGN_ICONS_SUBSET`, included here to be tested
+#[cfg(test)]
+mod subset_list;
+
+mod svg;
+pub use svg::SvgOptions;
 
 /// Public re-export of the `allsorts` crate
 pub use allsorts;
 
-/// A loaded font
-pub struct Font<'a>(allsorts::Font<DynamicFontTableProvider<'a>>);
+/// A loaded font  
+/// The font either borrows its data, or shares ownership of it - a `Font<'static>` can be stored
+/// in application state, or shared between threads in an [`Arc`]
+///
+/// The character map, glyph names and table directory are read once, when the font is loaded,
+/// so that glyphs can be looked up through a shared reference  
+/// Tables that take longer to read, such as the ligatures, are read the first time they are used,
+/// and kept for every clone of the font
+#[derive(Clone)]
+pub struct Font<'a> {
+    data: FontBytes<'a>,
+    tables: Arc<FontTables>,
+}
 impl<'a> Font<'a> {
     /// Load a font from a byte slice
     pub fn new(font_data: &'a [u8]) -> Result<Self, FontError> {
-        let scope = ReadScope::new(font_data);
-        let font = scope.read::<FontData<'_>>()?;
-        let provider = font.table_provider(0)?;
-        let font = allsorts::Font::new(provider)?;
-
-        Ok(Self(font))
+        Self::load(FontBytes::Borrowed(font_data))
     }
 
     /// Load the Google Material Design Icons font in the "Outlined" style
+    #[cfg(feature = "outlined")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "outlined")))]
     pub fn new_outlined() -> Result<Self, FontError> {
-        Self::new(crate::outlined::ICON_FONT)
+        Self::new(crate::outlined::icon_font())
     }
 
     /// Load the Google Material Design Icons font in the "Rounded" style
+    #[cfg(feature = "rounded")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rounded")))]
     pub fn new_rounded() -> Result<Self, FontError> {
-        Self::new(crate::rounded::ICON_FONT)
+        Self::new(crate::rounded::icon_font())
     }
 
     /// Load the Google Material Design Icons font in the "Sharp" style
+    #[cfg(feature = "sharp")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sharp")))]
     pub fn new_sharp() -> Result<Self, FontError> {
-        Self::new(crate::sharp::ICON_FONT)
+        Self::new(crate::sharp::icon_font())
     }
 
     /// Return the raw font data
-    pub fn font_data(&self) -> &allsorts::Font<DynamicFontTableProvider<'a>> {
-        &self.0
+    pub fn font_data(&self) -> &[u8] {
+        &self.data
     }
 
-    /// Return the raw font data as mutable
-    pub fn font_data_mut(&mut self) -> &mut allsorts::Font<DynamicFontTableProvider<'a>> {
-        &mut self.0
+    /// Parse the font with `allsorts`, for lookups that are not covered here  
+    /// The parsed font borrows from this one, and is not `Send` - parse it again on each thread that needs it
+    pub fn allsorts_font(&self) -> Result<allsorts::Font<DynamicFontTableProvider<'_>>, FontError> {
+        let font = ReadScope::new(&self.data).read::<FontData<'_>>()?;
+        let font = allsorts::Font::new(font.table_provider(0)?)?;
+        Ok(font)
     }
 
     /// Lookup a named glyph by it's index
     pub fn glyph_name(&self, id: u16) -> Option<Cow<'a, str>> {
-        let name = self.font_data().glyph_names(&[id]).pop()?;
-        Some(name)
+        self.tables.names.get(usize::from(id)).cloned()
+    }
+
+    /// Lookup a glyph ID by it's character code  
+    /// Characters that are not in the font map to glyph 0, `.notdef`
+    pub fn index_of(&self, codepoint: u32) -> Option<u16> {
+        std::char::from_u32(codepoint)?;
+        Some(self.tables.glyphs.get(&codepoint).copied().unwrap_or(0))
     }
 
-    /// Lookup a glyph ID by it's character code
-    pub fn index_of(&mut self, codepoint: u32) -> Option<u16> {
-        let char = std::char::from_u32(codepoint)?;
-        let (id, _) =
-            self.font_data_mut()
-                .lookup_glyph_index(char, MatchingPresentation::NotRequired, None);
-        Some(id)
+    /// Lookup a glyph ID by the name that spells out its ligature, such as `"home"`  
+    /// The name is matched against the ligatures that the font's `rlig` and `liga` features apply to Latin text,
+    /// so alias names that are not glyph names, such as `"home_filled"`, resolve to the canonical icon
+    ///
+    /// Returns None if the text does not form a single ligature  
+    /// Pass [`crate::IconName::ligature`] rather than the glyph name, which differs for icons starting with a digit
+    pub fn lookup_ligature(&self, name: &str) -> Option<u16> {
+        let components = name
+            .chars()
+            .map(|c| self.tables.glyphs.get(&u32::from(c)).copied())
+            .collect::<Option<Vec<_>>>()?;
+        self.ligatures().ok()?.get(&components).copied()
     }
 
     /// Lookup a bitmap for a glyph by it's ID
-    pub fn bitmap_for(&mut self, id: u16) -> Result<Option<BitmapGlyph>, FontError> {
+    pub fn bitmap_for(&self, id: u16) -> Result<Option<BitmapGlyph>, FontError> {
+        // Only parse the font if it has a table of images to look in
+        let provider = self.table_provider();
+        let images = [tag::SVG, tag::CBDT, tag::SBIX, tag::EBDT];
+        if !images.iter().any(|&tag| provider.has_table(tag)) {
+            return Ok(None);
+        }
+
         let bitmap = self
-            .font_data_mut()
+            .allsorts_font()?
             .lookup_glyph_image(id, 0, BitDepth::ThirtyTwo)?;
         Ok(bitmap)
     }
+
+    /// Read the ranges of the font's variable axes from its `fvar` table  
+    /// Axes that are not used by the Material Symbols fonts are left out
+    pub fn axis_ranges(&self) -> Result<Vec<AxisRange>, FontError> {
+        let Some(fvar_data) = self.table_provider().table(tag::FVAR)? else {
+            return Ok(Vec::new());
+        };
+
+        let fvar = ReadScope::new(fvar_data).read::<FvarTable<'_>>()?;
+        let ranges = fvar
+            .axes()
+            .filter_map(|record| {
+                Some(AxisRange {
+                    axis: Axis::from_tag(record.axis_tag)?,
+                    min: record.min_value.into(),
+                    default: record.default_value.into(),
+                    max: record.max_value.into(),
+                })
+            })
+            .collect();
+        Ok(ranges)
+    }
+
+    /// Check that a variation is supported by the font's variable axes
+    pub fn validate_variation(&self, variation: &IconVariation) -> Result<(), FontError> {
+        variation.validate(&self.axis_ranges()?)?;
+        Ok(())
+    }
+
+    /// Instantiate the font at a variation, as a static TTF with its own family name  
+    /// [`IconVariation::instance_family_name`] builds a name like "Material Symbols Sharp Filled 700"
+    ///
+    /// The static font draws the icons at that variation without any variable font support,
+    /// so it can be used by renderers that only pick fonts by family name
+    pub fn instance(
+        &self,
+        variation: &IconVariation,
+        family_name: &str,
+    ) -> Result<Vec<u8>, FontError> {
+        self.validate_variation(variation)?;
+        let axes = Axis::ALL
+            .iter()
+            .map(|&axis| (u32::from_be_bytes(axis.tag()), variation.value(axis)))
+            .collect::<Vec<_>>();
+
+        let data = instance::instance_font(&self.table_provider(), &axes, family_name)?;
+        Ok(data)
+    }
+
+    /// Cut the font down to the given icons, keeping their codepoints and ligatures  
+    /// Accepts icons, chars or raw codepoints - any that are not in the font are listed in [`FontSubset::missing`]
+    ///
+    /// The subset is a static font in the default style - the variation tables are not kept
+    pub fn subset<I: Into<u32> + Copy>(&self, icons: &[I]) -> Result<FontSubset, FontError> {
+        let mut glyph_ids = BTreeSet::new();
+        let mut missing = Vec::new();
+        for &icon in icons {
+            let codepoint = icon.into();
+            match self.tables.glyphs.get(&codepoint) {
+                Some(&id) if id != 0 => {
+                    glyph_ids.insert(id);
+                }
+                _ => missing.push(codepoint),
+            }
+        }
+
+        let data = subset::subset_font(&self.table_provider(), &glyph_ids)?;
+        Ok(FontSubset { data, missing })
+    }
+
+    /// Return every ligature in the font's GSUB table, as the ligature glyph and the glyphs it is made of
+    fn ligature_glyphs(&self) -> Result<Vec<(u16, Vec<u16>)>, FontError> {
+        let ligatures = self.ligatures()?;
+        Ok(ligatures
+            .iter()
+            .map(|(components, &id)| (id, components.clone()))
+            .collect())
+    }
+
+    /// Return the ligatures in the font's GSUB table, as the ligature glyph for each sequence of glyphs  
+    /// Where two ligatures are made of the same glyphs, the first one is kept, as it is the one the font draws
+    fn ligatures(&self) -> Result<&BTreeMap<Vec<u16>, u16>, FontError> {
+        if let Some(ligatures) = self.tables.ligatures.get() {
+            return Ok(ligatures);
+        }
+
+        let mut ligatures = BTreeMap::new();
+        for (id, components) in subset::ligature_glyphs(&self.table_provider())? {
+            ligatures.entry(components).or_insert(id);
+        }
+        Ok(self.tables.ligatures.get_or_init(|| ligatures))
+    }
+
+    /// Return the single substitutions in the font's GSUB table, or None if it has none
+    fn substitution_table(&self) -> Result<Option<&SubstitutionTable>, FontError> {
+        if let Some(table) = self.tables.substitutions.get() {
+            return Ok(table.as_ref());
+        }
+
+        let table = SubstitutionTable::read(&self.table_provider(), 0..self.tables.num_glyphs)?;
+        Ok(self.tables.substitutions.get_or_init(|| table).as_ref())
+    }
+
+    /// Return the normalized axis values and glyph substitutions for a variation  
+    /// Every variation used is kept, so drawing many icons at a handful of variations only reads each one once
+    fn varied_tables(&self, variation: &IconVariation) -> Result<Arc<VariedTables>, FontError> {
+        let cached = |varied: &[Arc<VariedTables>]| {
+            varied
+                .iter()
+                .find(|varied| varied.variation == *variation)
+                .cloned()
+        };
+        let lock = || {
+            self.tables
+                .varied
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+        };
+        if let Some(varied) = cached(&lock()) {
+            return Ok(varied);
+        }
+
+        // The lock is not held while reading, so that other variations can be drawn in the meantime
+        let varied = Arc::new(self.read_varied_tables(variation)?);
+
+        // Another thread may have read the same variation first, in which case its tables are kept
+        let mut cache = lock();
+        if let Some(varied) = cached(&cache) {
+            return Ok(varied);
+        }
+        cache.push(varied.clone());
+        Ok(varied)
+    }
+
+    /// Read the normalized axis values and glyph substitutions for a variation
+    fn read_varied_tables(&self, variation: &IconVariation) -> Result<VariedTables, FontError> {
+        self.validate_variation(variation)?;
+        let provider = self.table_provider();
+        let mut varied = VariedTables {
+            variation: *variation,
+            tuple: None,
+            substitutions: BTreeMap::new(),
+            advance_deltas: OnceLock::new(),
+        };
+
+        // Static fonts have no axes to vary, and the variation has already been checked against them
+        if let (Some(fvar), Some(_)) = (provider.table(tag::FVAR)?, provider.table(tag::GVAR)?) {
+            let fvar = ReadScope::new(fvar).read::<FvarTable<'_>>()?;
+            let avar = provider
+                .table(tag::AVAR)?
+                .map(|avar| ReadScope::new(avar).read::<AvarTable<'_>>())
+                .transpose()?;
+
+            let axes = Axis::ALL
+                .iter()
+                .map(|&axis| (u32::from_be_bytes(axis.tag()), variation.value(axis)))
+                .collect::<Vec<_>>();
+            let coordinates = instance::axis_coordinates(&fvar, &axes);
+            let tuple = fvar.normalize(coordinates.into_iter(), avar.as_ref())?;
+
+            if let Some(table) = self.substitution_table()? {
+                varied.substitutions = table.substitutions(tuple.as_tuple())?.into_iter().collect();
+            }
+            varied.tuple = Some(tuple);
+        }
+
+        Ok(varied)
+    }
+
+    /// Parse the font data, and read the tables used for lookups
+    fn load(data: FontBytes<'a>) -> Result<Self, FontError> {
+        let tables = {
+            let font = ReadScope::new(&data).read::<FontData<'_>>()?;
+            let directory = TableDirectory::read(&font)?;
+            let font = allsorts::Font::new(font.table_provider(0)?)?;
+            Arc::new(FontTables::new(&font, directory)?)
+        };
+        Ok(Self { data, tables })
+    }
+
+    /// Return a provider for the tables of the font, found through the directory read when it was loaded
+    fn table_provider(&self) -> TableProvider<'_> {
+        TableProvider {
+            data: &self.data,
+            directory: &self.tables.directory,
+        }
+    }
+}
+impl Font<'static> {
+    /// Load a font from an owned buffer
+    pub fn from_vec(font_data: Vec<u8>) -> Result<Self, FontError> {
+        Self::from_arc(font_data.into())
+    }
+
+    /// Load a font from a shared buffer, without copying it
+    pub fn from_arc(font_data: Arc<[u8]>) -> Result<Self, FontError> {
+        Self::load(FontBytes::Shared(font_data))
+    }
+
+    /// Load a font from a file
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, FontError> {
+        Self::from_vec(std::fs::read(path)?)
+    }
+}
+
+/// Lookup tables read from a font when it is loaded, or the first time they are used
+struct FontTables {
+    /// Where to find each of the font's tables
+    directory: TableDirectory,
+
+    /// Glyph IDs by character code
+    glyphs: BTreeMap<u32, u16>,
+
+    /// The first character code of each glyph
+    chars: BTreeMap<u16, u32>,
+
+    /// Glyph names by glyph ID, from the `post` table or failing that the `cmap` table
+    names: Vec<Cow<'static, str>>,
+
+    num_glyphs: u16,
+    units_per_em: u16,
+
+    /// Ligature glyphs by the glyphs they are made of, from the `GSUB` table
+    ligatures: OnceLock<BTreeMap<Vec<u16>, u16>>,
+
+    /// The offset of each glyph in the `glyf` table, from the `loca` table
+    glyph_offsets: OnceLock<Vec<u32>>,
+
+    /// The advance and side bearing of each glyph, from the `hmtx` table
+    horizontal_metrics: OnceLock<Vec<LongHorMetric>>,
+
+    /// The single substitutions in the `GSUB` table, for the glyphs variations swap in - None if there is no `GSUB` table
+    substitutions: OnceLock<Option<SubstitutionTable>>,
+
+    /// Every variation used to draw a glyph so far
+    varied: Mutex<Vec<Arc<VariedTables>>>,
+}
+impl FontTables {
+    fn new(
+        font: &allsorts::Font<DynamicFontTableProvider<'_>>,
+        directory: TableDirectory,
+    ) -> Result<Self, FontError> {
+        let cmap = ReadScope::new(font.cmap_subtable_data()).read::<CmapSubtable<'_>>()?;
+        let mut glyphs = BTreeMap::new();
+        let mut chars = BTreeMap::new();
+        cmap.mappings_fn(|codepoint, id| {
+            glyphs.insert(codepoint, id);
+            chars.entry(id).or_insert(codepoint);
+        })?;
+
+        let head = font.font_table_provider.read_table_data(tag::HEAD)?;
+        let head = ReadScope::new(&head).read::<HeadTable>()?;
+
+        let post = font.font_table_provider.table_data(tag::POST)?;
+        let post = post.map(|post| post.into_owned().into_boxed_slice());
+        let names = GlyphNames::new(&Some((font.cmap_subtable_encoding, cmap)), post);
+        let names = (0..font.num_glyphs())
+            .map(|id| names.glyph_name(id))
+            .collect();
+
+        Ok(Self {
+            directory,
+            glyphs,
+            chars,
+            names,
+            num_glyphs: font.num_glyphs(),
+            units_per_em: head.units_per_em,
+            ligatures: OnceLock::new(),
+            glyph_offsets: OnceLock::new(),
+            horizontal_metrics: OnceLock::new(),
+            substitutions: OnceLock::new(),
+            varied: Mutex::new(Vec::new()),
+        })
+    }
+}
+
+/// What a variation does to a font, as used to draw its glyphs
+pub(crate) struct VariedTables {
+    variation: IconVariation,
+
+    /// The normalized value of each axis, or None for a static font
+    tuple: Option<OwnedTuple>,
+
+    /// The glyphs the font's GSUB feature variations swap in, by the glyph they replace
+    substitutions: BTreeMap<u16, u16>,
+
+    /// How far the `HVAR` table moves the advance of each glyph, read the first time it is needed
+    advance_deltas: OnceLock<Vec<f32>>,
+}
+
+/// The location of each table in a font, by tag
+struct TableDirectory(BTreeMap<u32, TableData>);
+impl TableDirectory {
+    /// Read the table directory of a font  
+    /// Tables are kept as a range of the font data where possible, or copied out of fonts that compress them
+    fn read(font: &FontData<'_>) -> Result<Self, FontError> {
+        if let FontData::OpenType(OpenTypeFont {
+            data: OpenTypeData::Single(offsets),
+            ..
+        }) = font
+        {
+            let tables = offsets
+                .table_records
+                .iter()
+                .map(|record| {
+                    let start = record.offset as usize;
+                    let range = start..start + record.length as usize;
+                    (record.table_tag, TableData::Range(range))
+                })
+                .collect();
+            return Ok(Self(tables));
+        }
+
+        let provider = font.table_provider(0)?;
+        let mut tables = BTreeMap::new();
+        for tag in provider.table_tags().unwrap_or_default() {
+            if let Some(data) = provider.table_data(tag)? {
+                tables.insert(tag, TableData::Owned(data.into_owned().into_boxed_slice()));
+            }
+        }
+        Ok(Self(tables))
+    }
+}
+
+/// Where the data of a table is found
+enum TableData {
+    /// A range of the font data
+    Range(Range<usize>),
+
+    /// A copy of the table, for fonts that store it compressed or in a collection
+    Owned(Box<[u8]>),
+}
+
+/// A table provider for the tables of a [`Font`], which borrows them from its data
+struct TableProvider<'a> {
+    data: &'a [u8],
+    directory: &'a TableDirectory,
+}
+impl<'a> TableProvider<'a> {
+    /// Return the data of a table, or None if the font does not have it
+    fn table(&self, tag: u32) -> Result<Option<&'a [u8]>, ParseError> {
+        match self.directory.0.get(&tag) {
+            Some(TableData::Range(range)) => match self.data.get(range.clone()) {
+                Some(data) => Ok(Some(data)),
+                None => Err(ParseError::BadEof),
+            },
+            Some(TableData::Owned(data)) => Ok(Some(data)),
+            None => Ok(None),
+        }
+    }
+}
+impl FontTableProvider for TableProvider<'_> {
+    fn table_data(&self, tag: u32) -> Result<Option<Cow<'_, [u8]>>, ParseError> {
+        Ok(self.table(tag)?.map(Cow::Borrowed))
+    }
+
+    fn has_table(&self, tag: u32) -> bool {
+        self.directory.0.contains_key(&tag)
+    }
+
+    fn table_tags(&self) -> Option<Vec<u32>> {
+        Some(self.directory.0.keys().copied().collect())
+    }
+}
+
+/// The result of [`Font::subset`]
+#[derive(Debug, Clone)]
+pub struct FontSubset {
+    /// The subset font, as a static TTF
+    pub data: Vec<u8>,
+
+    /// The requested codepoints that are not in the font
+    pub missing: Vec<u32>,
+}
+
+/// The data behind a [`Font`]
+#[derive(Clone)]
+enum FontBytes<'a> {
+    Borrowed(&'a [u8]),
+    Shared(Arc<[u8]>),
+}
+impl std::ops::Deref for FontBytes<'_> {
+    type Target = [u8];
+    fn deref(&self) -> &[u8] {
+        match self {
+            FontBytes::Borrowed(data) => data,
+            FontBytes::Shared(data) => data,
+        }
+    }
 }
 
 /// A structure designed to map out the contents of a font
+#[derive(Clone)]
 pub struct FontMapper<'a> {
-    font: &'a Font<'a>,
-    cmap: CmapSubtable<'a>,
+    font: Font<'a>,
 }
 impl<'a> FontMapper<'a> {
-    pub fn new(font: &'a Font<'a>) -> Result<Self, FontError> {
-        let cmap_data = font.font_data().cmap_subtable_data();
-        let cmap = CmapSubtable::read(&mut ReadScope::new(cmap_data).ctxt())?;
-        Ok(Self { font, cmap })
+    pub fn new(font: &Font<'a>) -> Result<Self, FontError> {
+        Ok(Self { font: font.clone() })
     }
 
     /// Return all glyphs in the font
-    pub fn all_chars(&self) -> Result<Vec<Glyph>, FontError> {
-        let chars = self.cmap.mappings()?;
+    pub fn all_chars(&self) -> Result<Vec<Glyph<'a>>, FontError> {
+        let chars = &self.font.tables.chars;
         let mut glyphs = Vec::with_capacity(chars.len());
-        for (_, codepoint) in chars {
+        for &codepoint in chars.values() {
             let Some(glyph) = self.find_glyph(codepoint)? else {
                 continue;
             };
@@ -104,10 +560,48 @@ impl<'a> FontMapper<'a> {
         Ok(glyphs)
     }
 
+    /// Return every character in the font, sorted by codepoint  
+    /// Unlike [`FontMapper::all_chars`], glyphs mapped to several codepoints are returned once for each
+    pub fn all_codepoints(&self) -> Result<Vec<Glyph<'a>>, FontError> {
+        let mut glyphs = Vec::with_capacity(self.font.tables.glyphs.len());
+        for &codepoint in self.font.tables.glyphs.keys() {
+            if let Some(glyph) = self.find_glyph(codepoint)? {
+                glyphs.push(glyph);
+            }
+        }
+        Ok(glyphs)
+    }
+
+    /// Return every ligature in the font, sorted by name  
+    /// Several ligatures can spell out the same glyph - see [`Ligature::is_alias`]
+    ///
+    /// Upper and lowercase letters share glyphs, so the names are always given in lowercase
+    pub fn ligatures(&self) -> Result<Vec<Ligature<'a>>, FontError> {
+        let chars = &self.font.tables.chars;
+        let mut ligatures = Vec::new();
+        for (id, components) in self.font.ligature_glyphs()? {
+            let name = components
+                .iter()
+                .map(|id| chars.get(id).and_then(|&c| std::char::from_u32(c)))
+                .map(|c| c.map(|c| c.to_ascii_lowercase()))
+                .collect::<Option<String>>();
+            let (Some(name), Some(&codepoint)) = (name, chars.get(&id)) else {
+                continue;
+            };
+
+            let Some(glyph) = self.find_glyph(codepoint)? else {
+                continue;
+            };
+            ligatures.push(Ligature { name, glyph });
+        }
+
+        ligatures.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(ligatures)
+    }
+
     /// Return a [`Glyph`] by character code
-    pub fn find_glyph(&self, codepoint: u32) -> Result<Option<Glyph>, FontError> {
-        let id = self.cmap.map_glyph(codepoint)?;
-        let Some(id) = id else {
+    pub fn find_glyph(&self, codepoint: u32) -> Result<Option<Glyph<'a>>, FontError> {
+        let Some(&id) = self.font.tables.glyphs.get(&codepoint) else {
             return Ok(None);
         };
 
@@ -123,6 +617,28 @@ impl<'a> FontMapper<'a> {
     }
 }
 
+/// A ligature in a font - text that is drawn as a single icon
+#[derive(Debug, Clone)]
+pub struct Ligature<'a> {
+    /// The text that spells out the ligature, such as `"home_filled"`
+    pub name: String,
+
+    /// The glyph the ligature is drawn as, under its canonical name
+    pub glyph: Glyph<'a>,
+}
+impl Ligature<'_> {
+    /// Check if the ligature is an alias, spelled differently from the name of its glyph  
+    /// Glyph names starting with a digit are prefixed with `_`, which is not part of the ligature
+    pub fn is_alias(&self) -> bool {
+        let name = self
+            .glyph
+            .name
+            .strip_prefix('_')
+            .unwrap_or(&self.glyph.name);
+        self.name != name
+    }
+}
+
 /// Describes a single named glyph in a font  
 /// Contains the glyph's ID, character code, and name
 #[derive(Debug, Clone)]
@@ -163,7 +679,18 @@ impl BitmapExt for BitmapGlyph {
 pub enum FontError {
     ParseError(allsorts::error::ParseError),
     ReadWriteError(allsorts::error::ReadWriteError),
+    SubsetError(allsorts::subset::SubsetError),
+    VariationError(VariationError),
+    InstanceError(InstanceError),
     Io(std::io::Error),
+
+    /// The font has no glyph for the codepoint
+    MissingGlyph(u32),
+
+    /// A rendered icon could not be encoded as a PNG
+    #[cfg(feature = "png")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "png")))]
+    PngError(png::EncodingError),
 }
 impl From<allsorts::error::ParseError> for FontError {
     fn from(err: allsorts::error::ParseError) -> Self {
@@ -175,31 +702,85 @@ impl From<allsorts::error::ReadWriteError> for FontError {
         FontError::ReadWriteError(err)
     }
 }
+impl From<allsorts::subset::SubsetError> for FontError {
+    fn from(err: allsorts::subset::SubsetError) -> Self {
+        FontError::SubsetError(err)
+    }
+}
+impl From<VariationError> for FontError {
+    fn from(err: VariationError) -> Self {
+        FontError::VariationError(err)
+    }
+}
+impl From<InstanceError> for FontError {
+    fn from(err: InstanceError) -> Self {
+        FontError::InstanceError(err)
+    }
+}
 impl From<std::io::Error> for FontError {
     fn from(err: std::io::Error) -> Self {
         FontError::Io(err)
     }
 }
+#[cfg(feature = "png")]
+impl From<png::EncodingError> for FontError {
+    fn from(err: png::EncodingError) -> Self {
+        FontError::PngError(err)
+    }
+}
 impl std::fmt::Display for FontError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             FontError::ParseError(err) => write!(f, "Parse error: {}", err),
             FontError::ReadWriteError(err) => write!(f, "Read/write error: {}", err),
+            FontError::SubsetError(err) => write!(f, "Subset error: {}", err),
+            FontError::VariationError(err) => write!(f, "Variation error: {}", err),
+            FontError::InstanceError(err) => write!(f, "Instance error: {}", err),
             FontError::Io(err) => write!(f, "I/O error: {}", err),
+            FontError::MissingGlyph(codepoint) => {
+                write!(f, "No glyph for codepoint U+{:04X}", codepoint)
+            }
+            #[cfg(feature = "png")]
+            FontError::PngError(err) => write!(f, "PNG error: {}", err),
         }
     }
 }
 impl std::error::Error for FontError {}
 
+/// Helpers shared by the tests of the font modules
+#[cfg(all(test, any(feature = "outlined", feature = "rounded")))]
+mod test_fonts {
+    use super::Font;
+
+    /// Load the whole font for a style - when the build script subsets the embedded fonts,
+    /// they only keep some of the icons, and lose their glyph names
+    pub fn whole_font(style: crate::Style) -> Font<'static> {
+        #[cfg(not(icons_subset))]
+        let font = Font::new(style.icon_font());
+
+        #[cfg(icons_subset)]
+        let font = Font::from_path(format!(
+            "{}/src/fonts/{}.ttf",
+            env!("CARGO_MANIFEST_DIR"),
+            style.family_name().replace(' ', "")
+        ));
+
+        font.unwrap()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(any(feature = "outlined", feature = "rounded"))]
+    use test_fonts::whole_font;
 
     #[test]
+    #[cfg(feature = "outlined")]
     fn test_font() {
-        use crate::outlined::Icon;
+        use crate::IconName as Icon;
 
-        let mut font = Font::new_outlined().unwrap();
+        let font = whole_font(crate::Style::Outlined);
         let id = font.index_of(Icon::Neurology as u32).unwrap();
         let name = font.glyph_name(id).unwrap();
         assert_eq!(name, "neurology");
@@ -208,18 +789,349 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "outlined")]
+    fn test_concurrent_lookups() {
+        use crate::IconName as Icon;
+
+        let font = Arc::new(whole_font(crate::Style::Outlined));
+        let icons = [Icon::Add, Icon::Home, Icon::Delete, Icon::Neurology];
+        let threads = icons
+            .map(|icon| {
+                let font = Arc::clone(&font);
+                std::thread::spawn(move || {
+                    let id = font.index_of(icon as u32).unwrap();
+                    (
+                        id,
+                        font.glyph_name(id).unwrap(),
+                        font.lookup_ligature(icon.ligature()),
+                    )
+                })
+            })
+            .map(|thread| thread.join().unwrap());
+
+        for (icon, (id, name, ligature)) in icons.into_iter().zip(threads) {
+            assert_eq!(name, icon.name());
+            assert_eq!(ligature, Some(id));
+        }
+        assert_eq!(font.index_of(0x10FFFF), Some(0));
+        assert_eq!(font.index_of(0xD800), None);
+    }
+
+    #[test]
+    #[cfg(feature = "outlined")]
+    fn test_varied_tables_cache() {
+        let font = whole_font(crate::Style::Outlined);
+        let default = font.varied_tables(&IconVariation::DEFAULT).unwrap();
+        let filled = font.varied_tables(&IconVariation::FILLED).unwrap();
+        assert!(!Arc::ptr_eq(&default, &filled));
+        assert!(default.substitutions.is_empty());
+        assert!(!filled.substitutions.is_empty());
+
+        // Switching between variations reuses the tables read the first time, in every clone of the font
+        let clone = font.clone();
+        for _ in 0..2 {
+            let varied = font.varied_tables(&IconVariation::DEFAULT).unwrap();
+            assert!(Arc::ptr_eq(&varied, &default));
+            let varied = clone.varied_tables(&IconVariation::FILLED).unwrap();
+            assert!(Arc::ptr_eq(&varied, &filled));
+        }
+
+        // Threads drawing at different variations share the cache too
+        let font = Arc::new(font);
+        let variations = [
+            IconVariation::DEFAULT,
+            IconVariation::FILLED,
+            IconVariation::BOLD,
+        ];
+        let threads = variations
+            .map(|variation| {
+                let font = Arc::clone(&font);
+                std::thread::spawn(move || font.varied_tables(&variation).unwrap())
+            })
+            .map(|thread| thread.join().unwrap());
+        for (variation, varied) in variations.iter().zip(threads) {
+            assert!(Arc::ptr_eq(
+                &varied,
+                &font.varied_tables(variation).unwrap()
+            ));
+        }
+        assert!(Arc::ptr_eq(
+            &default,
+            &font.varied_tables(&IconVariation::DEFAULT).unwrap()
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "outlined")]
     fn test_font_mapper() {
-        let font = Font::new_outlined().unwrap();
+        let font = whole_font(crate::Style::Outlined);
         let mapper = FontMapper::new(&font).unwrap();
         let glyphs = mapper.all_chars().unwrap();
         assert!(!glyphs.is_empty());
     }
 
     #[test]
+    #[cfg(feature = "outlined")]
+    fn test_owned_font() {
+        use crate::IconName as Icon;
+
+        let font = whole_font(crate::Style::Outlined);
+        let data = font.font_data();
+        let fonts = [
+            Font::from_vec(data.to_vec()).unwrap(),
+            Font::from_arc(Arc::from(data)).unwrap(),
+        ];
+        for font in fonts {
+            assert_eq!(font.font_data(), data);
+            let id = font.index_of(Icon::Add as u32).unwrap();
+            assert_eq!(font.glyph_name(id).unwrap(), "add");
+        }
+
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/fonts/MaterialSymbolsOutlined.ttf"
+        );
+        let font = Font::from_path(path).unwrap();
+        assert_ne!(font.index_of(Icon::Add as u32).unwrap(), 0);
+
+        assert!(Font::from_vec(b"not a font".to_vec()).is_err());
+        assert!(matches!(
+            Font::from_path("not/a/font.ttf"),
+            Err(FontError::Io(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "outlined")]
+    fn test_shared_font() {
+        use crate::IconName as Icon;
+
+        fn assert_send_sync<T: Send + Sync + 'static>(_: &T) {}
+
+        let font = Font::from_vec(whole_font(crate::Style::Outlined).font_data().to_vec()).unwrap();
+        let mapper = Arc::new(FontMapper::new(&font).unwrap());
+        assert_send_sync(&font);
+        assert_send_sync(&mapper);
+
+        let threads = (0..4)
+            .map(|_| {
+                let mapper = Arc::clone(&mapper);
+                std::thread::spawn(move || mapper.find_glyph(Icon::Add as u32).unwrap().unwrap())
+            })
+            .collect::<Vec<_>>();
+        for thread in threads {
+            assert_eq!(thread.join().unwrap().name, "add");
+        }
+    }
+
+    #[test]
+    #[cfg(not(icons_subset))]
+    fn test_shared_icon_names() {
+        use crate::IconName;
+
+        for style in crate::Style::ALL {
+            let font = Font::new(style.icon_font()).unwrap();
+            let mapper = FontMapper::new(&font).unwrap();
+            for icon in IconName::iter() {
+                let glyph = mapper.find_glyph(icon as u32).unwrap().unwrap();
+                assert_eq!(glyph.name, icon.name());
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(icons_subset)]
+    fn test_embedded_subset() {
+        use crate::IconName;
+
+        for style in crate::Style::ALL {
+            let font = Font::new(style.icon_font()).unwrap();
+            let icons = IconName::iter()
+                .filter_map(|icon| Some((icon, font.index_of(icon as u32)?)))
+                .filter(|(_, id)| *id != 0)
+                .collect::<Vec<_>>();
+            assert!(!icons.is_empty() && icons.len() < IconName::COUNT);
+
+            // The icons that were kept can still be spelled out
+            for (icon, id) in icons {
+                assert_eq!(font.lookup_ligature(icon.ligature()), Some(id));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(all(
+        any(feature = "outlined", feature = "rounded", feature = "sharp"),
+        not(icons_subset)
+    ))]
+    fn test_style_conversions() {
+        use crate::{IconName, StyledIcon};
+
+        let mappers = crate::Style::ALL
+            .iter()
+            .map(|style| {
+                let font = Font::new(style.icon_font()).unwrap();
+                (*style, FontMapper::new(&font).unwrap())
+            })
+            .collect::<Vec<_>>();
+        let name_of = |mapper: &FontMapper<'_>, icon: StyledIcon| {
+            let glyph = mapper.find_glyph(icon.name as u32).unwrap().unwrap();
+            glyph.name.to_string()
+        };
+
+        // Switching style keeps the icon, and draws the glyph of the same name from the other font
+        for icon in IconName::iter() {
+            for (from, from_mapper) in &mappers {
+                let styled = icon.styled(*from);
+                let name = name_of(from_mapper, styled);
+                for (to, to_mapper) in &mappers {
+                    let converted = styled.with_style(*to);
+                    assert_eq!(converted.style, *to);
+                    assert_eq!(converted.with_style(*from), styled);
+                    assert_eq!(name_of(to_mapper, converted), name);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(icons_subset))]
+    fn test_axis_ranges() {
+        for style in crate::Style::ALL {
+            let font = Font::new(style.icon_font()).unwrap();
+            let ranges = font.axis_ranges().unwrap();
+            assert_eq!(ranges.len(), crate::Axis::ALL.len());
+
+            font.validate_variation(&IconVariation::DEFAULT).unwrap();
+            for range in ranges {
+                assert_eq!(range.default, IconVariation::DEFAULT.value(range.axis));
+            }
+
+            let variation = IconVariation::DEFAULT.with_weight(900.0);
+            assert!(font.validate_variation(&variation).is_err());
+        }
+    }
+
+    /// Return the raw outline data of every glyph in a font
+    #[cfg(all(feature = "sharp", not(icons_subset)))]
+    fn glyph_outlines(font: &Font) -> Vec<Vec<u8>> {
+        use allsorts::tables::{loca::LocaTable, HeadTable};
+
+        let font = font.allsorts_font().unwrap();
+        let provider = &font.font_table_provider;
+        let head = provider.read_table_data(tag::HEAD).unwrap();
+        let head = ReadScope::new(&head).read::<HeadTable>().unwrap();
+        let num_glyphs = usize::from(font.num_glyphs());
+        let loca = provider.read_table_data(tag::LOCA).unwrap();
+        let loca = ReadScope::new(&loca)
+            .read_dep::<LocaTable<'_>>((num_glyphs, head.index_to_loc_format))
+            .unwrap();
+        let glyf = provider.read_table_data(tag::GLYF).unwrap();
+
+        let offsets = loca.offsets.iter().collect::<Vec<_>>();
+        offsets
+            .windows(2)
+            .map(|w| glyf[w[0] as usize..w[1] as usize].to_vec())
+            .collect()
+    }
+
+    #[test]
+    #[cfg(feature = "sharp")]
+    #[cfg(not(icons_subset))]
+    fn test_instance() {
+        use crate::IconName as Icon;
+
+        let font = Font::new_sharp().unwrap();
+        let filled = IconVariation::DEFAULT.with_fill(1.0).with_weight(700.0);
+        let family_name = filled.instance_family_name("Material Symbols Sharp");
+        assert_eq!(family_name, "Material Symbols Sharp Filled 700");
+
+        let data = font.instance(&filled, &family_name).unwrap();
+        let instance = Font::new(&data).unwrap();
+        assert!(instance.axis_ranges().unwrap().is_empty());
+        let id = instance.index_of(Icon::Delete as u32).unwrap();
+
+        let parsed = instance.allsorts_font().unwrap();
+        let name = parsed
+            .font_table_provider
+            .read_table_data(tag::NAME)
+            .unwrap();
+        let name = ReadScope::new(&name)
+            .read::<allsorts::tables::NameTable<'_>>()
+            .unwrap();
+        let family = name.string_for_id(allsorts::tables::NameTable::FONT_FAMILY_NAME);
+        assert_eq!(family.as_deref(), Some(family_name.as_str()));
+
+        // The filled glyphs are drawn in place of the outlined ones
+        assert_eq!(instance.glyph_name(id).unwrap(), "delete");
+        let names = parsed.glyph_names(&(0..parsed.num_glyphs()).collect::<Vec<_>>());
+        let filled_id = names.iter().position(|name| name == "delete.fill").unwrap();
+        let outlines = glyph_outlines(&instance);
+        assert_eq!(outlines[usize::from(id)], outlines[filled_id]);
+
+        let unfilled = font
+            .instance(&filled.with_fill(0.0), "Material Symbols Sharp 700")
+            .unwrap();
+        let outlines = glyph_outlines(&Font::new(&unfilled).unwrap());
+        assert_ne!(outlines[usize::from(id)], outlines[filled_id]);
+
+        let out_of_range = IconVariation::DEFAULT.with_weight(900.0);
+        assert!(font.instance(&out_of_range, "Out of range").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "rounded")]
+    fn test_lookup_ligature() {
+        use crate::IconName as Icon;
+
+        let font = whole_font(crate::Style::Rounded);
+        let home = font.index_of(Icon::Home as u32).unwrap();
+        assert_eq!(font.lookup_ligature("home"), Some(home));
+        assert_eq!(font.lookup_ligature("home_filled"), Some(home));
+
+        let ten_k = font.index_of(Icon::_10k as u32).unwrap();
+        assert_eq!(font.lookup_ligature("10k"), Some(ten_k));
+        assert_eq!(font.lookup_ligature(Icon::_10k.ligature()), Some(ten_k));
+
+        assert_eq!(font.lookup_ligature("a"), None);
+        assert_eq!(font.lookup_ligature("not_an_icon"), None);
+        assert_eq!(font.lookup_ligature(""), None);
+    }
+
+    #[test]
+    #[cfg(feature = "rounded")]
+    fn test_ligatures() {
+        use crate::IconName;
+
+        let font = whole_font(crate::Style::Rounded);
+        let mapper = FontMapper::new(&font).unwrap();
+        let ligatures = mapper.ligatures().unwrap();
+        assert!(ligatures.windows(2).all(|w| w[0].name <= w[1].name));
+
+        let alias = ligatures
+            .iter()
+            .find(|ligature| ligature.name == "home_filled")
+            .unwrap();
+        assert!(alias.is_alias());
+        assert_eq!(alias.glyph.name, "home");
+        assert_eq!(alias.glyph.codepoint, IconName::Home as u32);
+
+        // Every icon can be spelled out by its own name
+        for icon in IconName::iter() {
+            let name = icon.name().trim_start_matches('_');
+            assert!(
+                ligatures.iter().any(|l| l.name == name && !l.is_alias()),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "outlined")]
     fn test_glyph() {
-        use crate::outlined::Icon;
+        use crate::IconName as Icon;
 
-        let font = Font::new_outlined().unwrap();
+        let font = whole_font(crate::Style::Outlined);
         let mapper = FontMapper::new(&font).unwrap();
         let glyph = mapper.find_glyph(Icon::Add as u32).unwrap().unwrap();
         assert_eq!(glyph.codepoint, Icon::Add as u32);
@@ -0,0 +1,469 @@
+//! Static instances of the variable fonts
+//!
+//! Fixes a variable font at one point on each of its axes, and renames it so that it can be
+//! installed or loaded next to the original.
+//!
+//! This file is also included by the build script, which bakes the instances embedded by the
+//! `filled`, `bold` and `filled-bold` features - so it must only depend on `allsorts`
+//!
+use allsorts::{
+    binary::{
+        read::ReadScope,
+        write::{WriteBinary, WriteBuffer},
+        U16Be, U32Be,
+    },
+    error::{ParseError, ReadWriteError},
+    font_data::FontData,
+    layout::{new_layout_cache, LayoutTable, SubstLookup, GSUB},
+    tables::{
+        owned,
+        variable_fonts::fvar::{FvarTable, Tuple},
+        Fixed, FontTableProvider, NameTable,
+    },
+    tag,
+    variations::VariationError,
+};
+use std::{borrow::Cow, collections::BTreeMap, ops::Range};
+
+/// Instantiate a variable font at the given axis values, as a static font with its own family name
+/// Axes are given by their `fvar` tag - any that are left out stay at their default value
+///
+/// Glyphs that the font swaps out at this point on its axes, such as the filled versions of the icons,
+/// are copied over the glyphs they replace - so the static font draws them without any shaping
+pub fn instance_font(
+    provider: &impl FontTableProvider,
+    axes: &[(u32, f32)],
+    family_name: &str,
+) -> Result<Vec<u8>, InstanceError> {
+    let fvar_data = provider.read_table_data(tag::FVAR)?;
+    let fvar = ReadScope::new(&fvar_data).read::<FvarTable<'_>>()?;
+    let coordinates = axis_coordinates(&fvar, axes);
+
+    let (data, tuple) = allsorts::variations::instance(provider, &coordinates)?;
+    let num_glyphs = read_u16(&provider.read_table_data(tag::MAXP)?, 4)?;
+    let substitutions = variation_substitutions(provider, tuple.as_tuple(), 0..num_glyphs)?;
+
+    let font = ReadScope::new(&data).read::<FontData<'_>>()?;
+    let instance = font.table_provider(0)?;
+    let mut tables = swap_glyphs(&instance, &substitutions)?;
+    tables.push((tag::NAME, rename(&instance, family_name)?));
+
+    let data = with_tables(&data, tables)?;
+    Ok(data)
+}
+
+/// Return a value for each axis in the `fvar` table, in its order and clamped to its range
+/// Axes are given by their tag - any that are left out stay at their default value
+pub fn axis_coordinates(fvar: &FvarTable<'_>, axes: &[(u32, f32)]) -> Vec<Fixed> {
+    fvar.axes()
+        .map(|record| {
+            let value = axes
+                .iter()
+                .find(|(tag, _)| *tag == record.axis_tag)
+                .map_or(record.default_value, |(_, value)| Fixed::from(*value));
+            value.clamp(record.min_value, record.max_value)
+        })
+        .collect()
+}
+
+/// Rebuild a font with some extra tables added, replacing any with the same tag
+pub fn with_tables(font: &[u8], tables: Vec<(u32, Vec<u8>)>) -> Result<Vec<u8>, ReadWriteError> {
+    let font = ReadScope::new(font).read::<FontData<'_>>()?;
+    let provider = ExtraTables {
+        provider: font.table_provider(0)?,
+        tables: tables.into_iter().collect(),
+    };
+
+    let tags = provider.table_tags().unwrap_or_default();
+    allsorts::subset::whole_font(&provider, &tags)
+}
+
+/// Return the single substitutions the font's GSUB feature variations make at a point on its axes,
+/// as the original glyph and the glyph that replaces it - only glyphs in the given range are checked
+pub fn variation_substitutions(
+    provider: &impl FontTableProvider,
+    tuple: Tuple<'_>,
+    glyphs: Range<u16>,
+) -> Result<Vec<(u16, u16)>, ParseError> {
+    match SubstitutionTable::read(provider, glyphs)? {
+        Some(table) => table.substitutions(tuple),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// The single substitutions in a font's GSUB table, read once so that the glyphs its feature variations
+/// swap in can be found at any number of points on its axes
+pub struct SubstitutionTable {
+    layout: LayoutTable<GSUB>,
+
+    /// The glyphs each single substitution lookup swaps in, as the original glyph and its replacement
+    lookups: BTreeMap<u16, Vec<(u16, u16)>>,
+}
+impl SubstitutionTable {
+    /// Read the GSUB table of a font, or return None if it has none  
+    /// Only glyphs in the given range are checked for substitutions
+    pub fn read(
+        provider: &impl FontTableProvider,
+        glyphs: Range<u16>,
+    ) -> Result<Option<Self>, ParseError> {
+        let Some(gsub_data) = provider.table_data(tag::GSUB)? else {
+            return Ok(None);
+        };
+
+        let gsub = ReadScope::new(&gsub_data).read::<LayoutTable<GSUB>>()?;
+        let cache = new_layout_cache(gsub);
+        let mut lookups = BTreeMap::new();
+        if let Some(lookup_list) = &cache.layout_table.opt_lookup_list {
+            // `allsorts` does not expose the number of lookups, so read it from the lookup list
+            let lookup_list_offset = usize::from(read_u16(&gsub_data, 8)?);
+            let num_lookups = read_u16(&gsub_data, lookup_list_offset)?;
+
+            for lookup_index in 0..num_lookups {
+                let lookup = lookup_list.lookup_cache_gsub(&cache, usize::from(lookup_index))?;
+                let SubstLookup::SingleSubst(subtables) = &lookup.lookup_subtables else {
+                    continue;
+                };
+
+                let mut substitutions = Vec::new();
+                for subtable in subtables {
+                    for glyph in glyphs.clone() {
+                        if let Some(substitute) = subtable.apply_glyph(glyph)? {
+                            substitutions.push((glyph, substitute));
+                        }
+                    }
+                }
+                lookups.insert(lookup_index, substitutions);
+            }
+        }
+
+        // The lookup cache cannot be shared between threads, so keep a layout table without it
+        let layout = ReadScope::new(&gsub_data).read::<LayoutTable<GSUB>>()?;
+        Ok(Some(Self { layout, lookups }))
+    }
+
+    /// Return the single substitutions the feature variations make at a point on the font's axes,
+    /// as the original glyph and the glyph that replaces it
+    pub fn substitutions(&self, tuple: Tuple<'_>) -> Result<Vec<(u16, u16)>, ParseError> {
+        let Some(features) = &self.layout.opt_feature_list else {
+            return Ok(Vec::new());
+        };
+        let Some(variations) = self.layout.feature_variations(Some(tuple))? else {
+            return Ok(Vec::new());
+        };
+
+        let mut substitutions = Vec::new();
+        let mut index = 0;
+        while features.nth_feature_record(index).is_ok() {
+            let feature = variations.substitute(index as u16);
+            index += 1;
+
+            for lookup_index in feature.map(|f| f.lookup_indices).unwrap_or_default() {
+                if let Some(lookup) = self.lookups.get(&lookup_index) {
+                    substitutions.extend(lookup);
+                }
+            }
+        }
+
+        Ok(substitutions)
+    }
+}
+
+/// Copy the outline and metrics of each substitute glyph over the glyph it replaces
+/// Returns the new `glyf`, `loca`, `head` and `hmtx` tables - `loca` is always written in the long format
+fn swap_glyphs(
+    provider: &impl FontTableProvider,
+    substitutions: &[(u16, u16)],
+) -> Result<Vec<(u32, Vec<u8>)>, ParseError> {
+    if substitutions.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut head = provider.read_table_data(tag::HEAD)?.into_owned();
+    let num_glyphs = usize::from(read_u16(&provider.read_table_data(tag::MAXP)?, 4)?);
+    let num_h_metrics = usize::from(read_u16(&provider.read_table_data(tag::HHEA)?, 34)?);
+    let glyf = provider.read_table_data(tag::GLYF)?;
+    let loca = provider.read_table_data(tag::LOCA)?;
+    let hmtx = provider.read_table_data(tag::HMTX)?;
+    let num_short_metrics = num_glyphs.saturating_sub(num_h_metrics);
+    if num_h_metrics == 0 || hmtx.len() < 4 * num_h_metrics + 2 * num_short_metrics {
+        return Err(ParseError::BadIndex);
+    }
+
+    // Glyphs past the end of the long metrics share the last advance, and only store their bearing
+    let bearing_offset = |glyph: usize| match glyph < num_h_metrics {
+        true => 4 * glyph + 2,
+        false => 4 * num_h_metrics + 2 * (glyph - num_h_metrics),
+    };
+    let mut new_hmtx = hmtx.to_vec();
+
+    let long_loca = read_u16(&head, 50)? == 1;
+    let offsets = (0..=num_glyphs)
+        .map(|i| match long_loca {
+            true => read_u32(&loca, 4 * i).map(|offset| offset as usize),
+            false => read_u16(&loca, 2 * i).map(|offset| 2 * usize::from(offset)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut sources = (0..num_glyphs).collect::<Vec<_>>();
+    for &(glyph, substitute) in substitutions {
+        let (glyph, substitute) = (usize::from(glyph), usize::from(substitute));
+        if glyph >= num_glyphs || substitute >= num_glyphs {
+            return Err(ParseError::BadIndex);
+        }
+        sources[glyph] = substitute;
+
+        let (from, to) = (bearing_offset(substitute), bearing_offset(glyph));
+        new_hmtx[to..to + 2].copy_from_slice(&hmtx[from..from + 2]);
+
+        // Only glyphs with a long metric have an advance of their own - the rest keep the shared one
+        if glyph < num_h_metrics {
+            let from = 4 * substitute.min(num_h_metrics - 1);
+            new_hmtx[4 * glyph..4 * glyph + 2].copy_from_slice(&hmtx[from..from + 2]);
+        }
+    }
+
+    let mut new_glyf = Vec::with_capacity(glyf.len());
+    let mut new_loca = Vec::with_capacity(4 * (num_glyphs + 1));
+    for source in sources {
+        let outline = glyf
+            .get(offsets[source]..offsets[source + 1])
+            .ok_or(ParseError::BadOffset)?;
+        new_loca.extend((new_glyf.len() as u32).to_be_bytes());
+        new_glyf.extend(outline);
+
+        // Glyph data must stay 4-byte aligned
+        new_glyf.resize(new_glyf.len().next_multiple_of(4), 0);
+    }
+    new_loca.extend((new_glyf.len() as u32).to_be_bytes());
+    head.get_mut(50..52)
+        .ok_or(ParseError::BadEof)?
+        .copy_from_slice(&1u16.to_be_bytes());
+
+    Ok(vec![
+        (tag::GLYF, new_glyf),
+        (tag::LOCA, new_loca),
+        (tag::HEAD, head),
+        (tag::HMTX, new_hmtx),
+    ])
+}
+
+/// Build a name table giving the font a new family name, in the "Regular" subfamily
+fn rename(provider: &impl FontTableProvider, family_name: &str) -> Result<Vec<u8>, ReadWriteError> {
+    let name_data = provider.read_table_data(tag::NAME)?;
+    let name = ReadScope::new(&name_data).read::<NameTable<'_>>()?;
+    let mut name = owned::NameTable::try_from(&name)?;
+
+    // PostScript names are limited to printable ASCII, without spaces
+    let postscript_name = family_name
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+        .collect::<String>()
+        + "-Regular";
+
+    let entries = [
+        (NameTable::FONT_FAMILY_NAME, family_name),
+        (NameTable::FONT_SUBFAMILY_NAME, "Regular"),
+        (NameTable::UNIQUE_FONT_IDENTIFIER, &postscript_name),
+        (NameTable::FULL_FONT_NAME, family_name),
+        (NameTable::POSTSCRIPT_NAME, &postscript_name),
+        (NameTable::TYPOGRAPHIC_FAMILY_NAME, family_name),
+        (NameTable::TYPOGRAPHIC_SUBFAMILY_NAME, "Regular"),
+    ];
+    name.remove_entries(NameTable::VARIATIONS_POSTSCRIPT_NAME_PREFIX);
+    for (name_id, string) in entries {
+        name.replace_entries(name_id, string);
+
+        // Some platforms only read the Windows names, so add an English one of those too
+        name.name_records.push(owned::NameRecord {
+            platform_id: 3,
+            encoding_id: 1,
+            language_id: 0x0409,
+            name_id,
+            string: Cow::Owned(string.encode_utf16().flat_map(u16::to_be_bytes).collect()),
+        });
+    }
+
+    // Records must be sorted by platform, encoding, language and name ID
+    name.name_records.sort_by_key(|record| {
+        (
+            record.platform_id,
+            record.encoding_id,
+            record.language_id,
+            record.name_id,
+        )
+    });
+
+    let mut buffer = WriteBuffer::new();
+    owned::NameTable::write(&mut buffer, &name)?;
+    Ok(buffer.into_inner())
+}
+
+/// Read a big-endian 16-bit value from a table
+fn read_u16(table: &[u8], offset: usize) -> Result<u16, ParseError> {
+    ReadScope::new(table).offset(offset).ctxt().read::<U16Be>()
+}
+
+/// Read a big-endian 32-bit value from a table
+fn read_u32(table: &[u8], offset: usize) -> Result<u32, ParseError> {
+    ReadScope::new(table).offset(offset).ctxt().read::<U32Be>()
+}
+
+/// A table provider that adds extra tables on top of an existing font
+struct ExtraTables<P> {
+    provider: P,
+    tables: BTreeMap<u32, Vec<u8>>,
+}
+impl<P: FontTableProvider> FontTableProvider for ExtraTables<P> {
+    fn table_data(&self, tag: u32) -> Result<Option<Cow<'_, [u8]>>, ParseError> {
+        match self.tables.get(&tag) {
+            Some(data) => Ok(Some(Cow::Borrowed(data))),
+            None => self.provider.table_data(tag),
+        }
+    }
+
+    fn has_table(&self, tag: u32) -> bool {
+        self.tables.contains_key(&tag) || self.provider.has_table(tag)
+    }
+
+    fn table_tags(&self) -> Option<Vec<u32>> {
+        let mut tags = self.provider.table_tags()?;
+        tags.extend(self.tables.keys());
+        tags.sort_unstable();
+        tags.dedup();
+        Some(tags)
+    }
+}
+
+/// Error type for instancing a font
+#[derive(Debug)]
+pub enum InstanceError {
+    Parse(ParseError),
+    ReadWrite(ReadWriteError),
+    Variation(VariationError),
+}
+impl From<ParseError> for InstanceError {
+    fn from(err: ParseError) -> Self {
+        InstanceError::Parse(err)
+    }
+}
+impl From<ReadWriteError> for InstanceError {
+    fn from(err: ReadWriteError) -> Self {
+        InstanceError::ReadWrite(err)
+    }
+}
+impl From<VariationError> for InstanceError {
+    fn from(err: VariationError) -> Self {
+        InstanceError::Variation(err)
+    }
+}
+impl std::fmt::Display for InstanceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InstanceError::Parse(err) => write!(f, "Parse error: {}", err),
+            InstanceError::ReadWrite(err) => write!(f, "Read/write error: {}", err),
+            InstanceError::Variation(err) => write!(f, "Variation error: {}", err),
+        }
+    }
+}
+impl std::error::Error for InstanceError {}
+
+#[cfg(all(test, feature = "sharp"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swap_glyphs_truncated_hmtx() {
+        let font = ReadScope::new(crate::sharp::icon_font())
+            .read::<FontData<'_>>()
+            .unwrap();
+        let provider = ExtraTables {
+            provider: font.table_provider(0).unwrap(),
+            tables: BTreeMap::from([(tag::HMTX, Vec::new())]),
+        };
+
+        // The hhea table promises more metrics than the hmtx table holds
+        assert!(matches!(
+            swap_glyphs(&provider, &[(0, 0)]),
+            Err(ParseError::BadIndex)
+        ));
+    }
+
+    #[test]
+    fn test_swap_glyphs_short_metrics() {
+        let font = ReadScope::new(crate::sharp::icon_font())
+            .read::<FontData<'_>>()
+            .unwrap();
+        let provider = font.table_provider(0).unwrap();
+        let num_glyphs =
+            usize::from(read_u16(&provider.read_table_data(tag::MAXP).unwrap(), 4).unwrap());
+        let hmtx = provider.read_table_data(tag::HMTX).unwrap();
+        let mut hhea = provider.read_table_data(tag::HHEA).unwrap().into_owned();
+        let num_h_metrics = usize::from(read_u16(&hhea, 34).unwrap());
+        let bearings = (0..num_glyphs)
+            .map(|glyph| match glyph < num_h_metrics {
+                true => read_u16(&hmtx, 4 * glyph + 2).unwrap(),
+                false => read_u16(&hmtx, 4 * num_h_metrics + 2 * (glyph - num_h_metrics)).unwrap(),
+            })
+            .collect::<Vec<_>>();
+
+        // Rewrite the font with a single long metric, leaving only the bearings of the other glyphs
+        let mut short_hmtx = hmtx[..4].to_vec();
+        short_hmtx.extend(bearings[1..].iter().flat_map(|lsb| lsb.to_be_bytes()));
+        hhea[34..36].copy_from_slice(&1u16.to_be_bytes());
+
+        let provider = ExtraTables {
+            provider,
+            tables: BTreeMap::from([(tag::HMTX, short_hmtx), (tag::HHEA, hhea)]),
+        };
+        let glyph = 1;
+        let substitute = (2..num_glyphs)
+            .find(|&glyph| bearings[glyph] != bearings[1])
+            .unwrap();
+
+        let tables = swap_glyphs(&provider, &[(glyph as u16, substitute as u16)]).unwrap();
+        let (_, new_hmtx) = tables.iter().find(|(tag, _)| *tag == tag::HMTX).unwrap();
+        assert_eq!(read_u16(new_hmtx, 4).unwrap(), bearings[substitute]);
+        assert_eq!(
+            read_u16(new_hmtx, 4 + 2 * (substitute - 1)).unwrap(),
+            bearings[substitute]
+        );
+    }
+
+    #[test]
+    #[cfg(not(icons_subset))]
+    fn test_instance_bearings() {
+        let font = ReadScope::new(crate::sharp::icon_font())
+            .read::<FontData<'_>>()
+            .unwrap();
+        let provider = font.table_provider(0).unwrap();
+        let data =
+            instance_font(&provider, &[(u32::from_be_bytes(*b"FILL"), 1.0)], "Filled").unwrap();
+
+        let font = ReadScope::new(&data).read::<FontData<'_>>().unwrap();
+        let instance = font.table_provider(0).unwrap();
+        let table = |tag| instance.read_table_data(tag).unwrap();
+        let (head, hhea, maxp) = (table(tag::HEAD), table(tag::HHEA), table(tag::MAXP));
+        let (glyf, loca, hmtx) = (table(tag::GLYF), table(tag::LOCA), table(tag::HMTX));
+
+        let num_glyphs = usize::from(read_u16(&maxp, 4).unwrap());
+        let num_h_metrics = usize::from(read_u16(&hhea, 34).unwrap());
+        let long_loca = read_u16(&head, 50).unwrap() == 1;
+        let offset = |glyph: usize| match long_loca {
+            true => read_u32(&loca, 4 * glyph).unwrap() as usize,
+            false => 2 * usize::from(read_u16(&loca, 2 * glyph).unwrap()),
+        };
+
+        // The left side bearing of every glyph with an outline is its xMin
+        for glyph in 0..num_glyphs {
+            if offset(glyph) == offset(glyph + 1) {
+                continue;
+            }
+            let x_min = read_u16(&glyf, offset(glyph) + 2).unwrap();
+            let lsb = match glyph < num_h_metrics {
+                true => read_u16(&hmtx, 4 * glyph + 2).unwrap(),
+                false => read_u16(&hmtx, 4 * num_h_metrics + 2 * (glyph - num_h_metrics)).unwrap(),
+            };
+            assert_eq!(lsb, x_min, "glyph {glyph}");
+        }
+    }
+}
@@ -0,0 +1,391 @@
+//! Glyph metrics
+//!
+//! Reads how much room each glyph takes up on a line, and the font's vertical metrics, for laying icons out next to text
+//!
+use super::{Font, FontError, IconPath, PathCommand, Point, VariedTables};
+use crate::IconVariation;
+use allsorts::{
+    binary::read::ReadScope,
+    error::ParseError,
+    tables::{
+        os2::{FsSelection, Os2},
+        variable_fonts::hvar::HvarTable,
+        FontTableProvider, HeadTable, HheaTable, HmtxTable, LongHorMetric,
+    },
+    tag,
+};
+
+/// The horizontal metrics of a glyph, in font units
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlyphMetrics {
+    /// How far along the baseline the next glyph starts
+    pub advance_width: f32,
+
+    /// The space between the glyph's origin and the leftmost point of its outline  
+    /// As with the `hmtx` table, this counts the control points of curves, like the bounds in the `glyf` table -
+    /// so it can be less than `bounds.x_min` where a control point lies outside the curve
+    pub left_side_bearing: f32,
+
+    /// The tight bounds of the glyph's outline, or None if it draws nothing
+    pub bounds: Option<BoundingBox>,
+}
+
+/// A rectangle in font units, with y pointing up from the baseline
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct BoundingBox {
+    pub x_min: f32,
+    pub y_min: f32,
+    pub x_max: f32,
+    pub y_max: f32,
+}
+impl BoundingBox {
+    pub fn width(&self) -> f32 {
+        self.x_max - self.x_min
+    }
+
+    pub fn height(&self) -> f32 {
+        self.y_max - self.y_min
+    }
+
+    /// Grow the box to include a point
+    fn include(&mut self, point: Point) {
+        self.x_min = self.x_min.min(point.x);
+        self.y_min = self.y_min.min(point.y);
+        self.x_max = self.x_max.max(point.x);
+        self.y_max = self.y_max.max(point.y);
+    }
+}
+
+/// The vertical metrics shared by every glyph in a font, in font units
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerticalMetrics {
+    /// The size of the em square
+    pub units_per_em: u16,
+
+    /// The distance from the baseline to the top of a line
+    pub ascender: i16,
+
+    /// The distance from the baseline to the bottom of a line - negative, as it is below the baseline
+    pub descender: i16,
+
+    /// Extra space to leave between lines
+    pub line_gap: i16,
+}
+
+impl Font<'_> {
+    /// Return the metrics of a glyph, as stored in the font  
+    /// The advance and side bearing are read from the `hmtx` table, and the bounds are measured from the glyph's outline
+    ///
+    /// See [`Font::metrics_at`] for the metrics of a glyph at a variation
+    pub fn metrics(&self, glyph_id: u16) -> Result<GlyphMetrics, FontError> {
+        let path = self.outline(glyph_id, &IconVariation::DEFAULT)?;
+        let metric = self.horizontal_metric(glyph_id)?;
+        Ok(GlyphMetrics {
+            advance_width: f32::from(metric.advance_width),
+            left_side_bearing: f32::from(metric.lsb),
+            bounds: path.bounds(),
+        })
+    }
+
+    /// Return the metrics of a glyph at a variation  
+    /// The advance is adjusted by the `HVAR` table, or by the `gvar` deltas for the glyph's phantom points
+    /// in fonts without one - the side bearing and bounds are measured from the varied outline,
+    /// the side bearing counting its control points as the `hmtx` table does
+    ///
+    /// Like [`Font::outline`], this follows the glyphs the `FILL` axis swaps in
+    pub fn metrics_at(
+        &self,
+        glyph_id: u16,
+        variation: &IconVariation,
+    ) -> Result<GlyphMetrics, FontError> {
+        let outline = self.varied_outline(glyph_id, variation)?;
+        let metric = self.horizontal_metric(outline.glyph_id)?;
+
+        // The first two phantom points are the glyph's origin, and the end of its advance
+        let [origin, advance, ..] = outline.phantom_deltas;
+        let advance_delta = match self.advance_deltas(&outline.varied)? {
+            Some(deltas) => *deltas
+                .get(usize::from(outline.glyph_id))
+                .ok_or(ParseError::BadIndex)?,
+            None => advance.x - origin.x,
+        };
+
+        // The side bearing runs to the leftmost point of the glyph, as in its `glyf` bounds
+        let x_min = outline
+            .path
+            .commands
+            .iter()
+            .flat_map(|command| match *command {
+                PathCommand::MoveTo(to) | PathCommand::LineTo(to) => vec![to.x],
+                PathCommand::QuadTo(control, to) => vec![control.x, to.x],
+                PathCommand::Close => Vec::new(),
+            })
+            .reduce(f32::min);
+        let left_side_bearing = match x_min {
+            Some(x_min) => x_min - origin.x,
+            None => f32::from(metric.lsb),
+        };
+
+        Ok(GlyphMetrics {
+            advance_width: f32::from(metric.advance_width) + advance_delta,
+            left_side_bearing,
+            bounds: outline.path.bounds(),
+        })
+    }
+
+    /// Return the vertical metrics of the font  
+    /// The ascender, descender and line gap are read from the `hhea` table - or from the `OS/2` table,
+    /// if the font asks for its typographic metrics to be used
+    pub fn vertical_metrics(&self) -> Result<VerticalMetrics, FontError> {
+        let provider = self.table_provider();
+        let head = provider.read_table_data(tag::HEAD)?;
+        let head = ReadScope::new(&head).read::<HeadTable>()?;
+        let hhea = provider.read_table_data(tag::HHEA)?;
+        let hhea = ReadScope::new(&hhea).read::<HheaTable>()?;
+
+        let os2 = provider.table_data(tag::OS_2)?;
+        let os2 = os2
+            .as_ref()
+            .map(|os2| ReadScope::new(os2).read_dep::<Os2>(os2.len()))
+            .transpose()?;
+        let typo_metrics = os2
+            .filter(|os2| os2.fs_selection.contains(FsSelection::USE_TYPO_METRICS))
+            .and_then(|os2| os2.version0);
+
+        let (ascender, descender, line_gap) = match typo_metrics {
+            Some(typo) => (
+                typo.s_typo_ascender,
+                typo.s_typo_descender,
+                typo.s_typo_line_gap,
+            ),
+            None => (hhea.ascender, hhea.descender, hhea.line_gap),
+        };
+
+        Ok(VerticalMetrics {
+            units_per_em: head.units_per_em,
+            ascender,
+            descender,
+            line_gap,
+        })
+    }
+
+    /// Return the advance and side bearing of a glyph from the `hmtx` table
+    fn horizontal_metric(&self, glyph_id: u16) -> Result<LongHorMetric, FontError> {
+        let metrics = match self.tables.horizontal_metrics.get() {
+            Some(metrics) => metrics,
+            None => {
+                let metrics = self.read_horizontal_metrics()?;
+                self.tables.horizontal_metrics.get_or_init(|| metrics)
+            }
+        };
+
+        let metric = metrics.get(usize::from(glyph_id));
+        Ok(*metric.ok_or(ParseError::BadIndex)?)
+    }
+
+    /// Read the advance and side bearing of every glyph from the `hmtx` table
+    fn read_horizontal_metrics(&self) -> Result<Vec<LongHorMetric>, FontError> {
+        let provider = self.table_provider();
+        let hhea = provider.read_table_data(tag::HHEA)?;
+        let hhea = ReadScope::new(&hhea).read::<HheaTable>()?;
+        let hmtx = provider.read_table_data(tag::HMTX)?;
+        let hmtx = ReadScope::new(&hmtx).read_dep::<HmtxTable<'_>>((
+            usize::from(self.tables.num_glyphs),
+            usize::from(hhea.num_h_metrics),
+        ))?;
+
+        let metrics = (0..self.tables.num_glyphs)
+            .map(|id| hmtx.metric(id))
+            .collect::<Result<_, _>>()?;
+        Ok(metrics)
+    }
+
+    /// Return how far the `HVAR` table moves the advance of each glyph at a variation,
+    /// or None if the font is static or has no `HVAR` table
+    fn advance_deltas<'v>(&self, varied: &'v VariedTables) -> Result<Option<&'v [f32]>, FontError> {
+        let (Some(tuple), Some(hvar)) = (&varied.tuple, self.table_provider().table(tag::HVAR)?)
+        else {
+            return Ok(None);
+        };
+        if let Some(deltas) = varied.advance_deltas.get() {
+            return Ok(Some(deltas));
+        }
+
+        let hvar = ReadScope::new(hvar).read::<HvarTable<'_>>()?;
+        let deltas = (0..self.tables.num_glyphs)
+            .map(|id| hvar.advance_delta(tuple, id))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Some(varied.advance_deltas.get_or_init(|| deltas)))
+    }
+}
+
+impl IconPath {
+    /// Return the tight bounds of the path, or None if it is empty  
+    /// Curves are measured to their furthest extent, rather than to their control points
+    pub fn bounds(&self) -> Option<BoundingBox> {
+        let mut bounds: Option<BoundingBox> = None;
+        let mut include = |point: Point| match &mut bounds {
+            Some(bounds) => bounds.include(point),
+            None => {
+                bounds = Some(BoundingBox {
+                    x_min: point.x,
+                    y_min: point.y,
+                    x_max: point.x,
+                    y_max: point.y,
+                })
+            }
+        };
+
+        let mut current = Point::default();
+        for command in &self.commands {
+            match *command {
+                PathCommand::MoveTo(to) | PathCommand::LineTo(to) => {
+                    include(to);
+                    current = to;
+                }
+                PathCommand::QuadTo(control, to) => {
+                    include(to);
+                    for t in [
+                        extremum(current.x, control.x, to.x),
+                        extremum(current.y, control.y, to.y),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    {
+                        let u = 1.0 - t;
+                        include(Point::new(
+                            u * u * current.x + 2.0 * u * t * control.x + t * t * to.x,
+                            u * u * current.y + 2.0 * u * t * control.y + t * t * to.y,
+                        ));
+                    }
+                    current = to;
+                }
+                PathCommand::Close => {}
+            }
+        }
+
+        bounds
+    }
+}
+
+/// Return where a quadratic curve turns back on one axis, as a fraction of the way along it  
+/// Returns None if the curve keeps moving the same way from start to end
+fn extremum(from: f32, control: f32, to: f32) -> Option<f32> {
+    let denominator = from - 2.0 * control + to;
+    if denominator == 0.0 {
+        return None;
+    }
+
+    let t = (from - control) / denominator;
+    (t > 0.0 && t < 1.0).then_some(t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds() {
+        let p = |x, y| Point::new(x, y);
+        let mut path = IconPath {
+            commands: vec![
+                PathCommand::MoveTo(p(0.0, 0.0)),
+                PathCommand::QuadTo(p(5.0, 10.0), p(10.0, 0.0)),
+                PathCommand::LineTo(p(10.0, -2.0)),
+                PathCommand::Close,
+            ],
+            units_per_em: 10,
+        };
+
+        // The curve peaks halfway between its control point and the baseline
+        let bounds = path.bounds().unwrap();
+        assert_eq!(
+            bounds,
+            BoundingBox {
+                x_min: 0.0,
+                y_min: -2.0,
+                x_max: 10.0,
+                y_max: 5.0
+            }
+        );
+        assert_eq!((bounds.width(), bounds.height()), (10.0, 7.0));
+
+        path.commands.clear();
+        assert_eq!(path.bounds(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "sharp")]
+    #[cfg(not(icons_subset))]
+    fn test_metrics() {
+        use crate::IconName as Icon;
+
+        let font = Font::new_sharp().unwrap();
+        let add = font.index_of(Icon::Add as u32).unwrap();
+        let metrics = font.metrics(add).unwrap();
+        assert_eq!(metrics.advance_width, 960.0);
+        assert_eq!(metrics.left_side_bearing, 200.0);
+        assert_eq!(
+            metrics.bounds,
+            Some(BoundingBox {
+                x_min: 200.0,
+                y_min: 200.0,
+                x_max: 760.0,
+                y_max: 760.0
+            })
+        );
+        assert_eq!(
+            font.metrics_at(add, &IconVariation::DEFAULT).unwrap(),
+            metrics
+        );
+
+        // Bolder strokes spread the outline out, but every icon keeps the same advance
+        let bold = font.metrics_at(add, &IconVariation::BOLD).unwrap();
+        let bounds = bold.bounds.unwrap();
+        assert_eq!(bold.advance_width, 960.0);
+        assert!(bounds.x_min < 200.0 && bounds.x_max > 760.0);
+
+        // The side bearing runs to the leftmost point of the outline, control points included,
+        // while the bounds follow the curves - so it is never right of them
+        for icon in [Icon::Add, Icon::Home, Icon::Favorite, Icon::Circle] {
+            let id = font.index_of(icon as u32).unwrap();
+            for variation in [IconVariation::DEFAULT, IconVariation::BOLD] {
+                let path = font.outline(id, &variation).unwrap();
+                let x_min = path
+                    .commands
+                    .iter()
+                    .flat_map(|command| match *command {
+                        PathCommand::MoveTo(to) | PathCommand::LineTo(to) => vec![to.x],
+                        PathCommand::QuadTo(control, to) => vec![control.x, to.x],
+                        PathCommand::Close => Vec::new(),
+                    })
+                    .fold(f32::INFINITY, f32::min);
+
+                let metrics = font.metrics_at(id, &variation).unwrap();
+                assert_eq!(metrics.left_side_bearing, x_min, "{icon:?}");
+                assert!(metrics.left_side_bearing <= metrics.bounds.unwrap().x_min);
+                if variation == IconVariation::DEFAULT {
+                    assert_eq!(font.metrics(id).unwrap(), metrics, "{icon:?}");
+                }
+            }
+        }
+
+        let space = font.index_of(' ' as u32).unwrap();
+        assert_eq!(font.metrics(space).unwrap().bounds, None);
+    }
+
+    #[test]
+    #[cfg(feature = "sharp")]
+    fn test_vertical_metrics() {
+        let font = Font::new_sharp().unwrap();
+        assert_eq!(
+            font.vertical_metrics().unwrap(),
+            VerticalMetrics {
+                units_per_em: 960,
+                ascender: 1056,
+                descender: -96,
+                line_gap: 0,
+            }
+        );
+    }
+}
@@ -0,0 +1,915 @@
+//! Glyph outlines
+//!
+//! Reads the contours of a glyph from the `glyf` table, moved by the `gvar` deltas for a point on the font's axes
+//!
+use super::{Font, FontError, VariedTables};
+use crate::IconVariation;
+use allsorts::{
+    binary::read::{ReadCtxt, ReadScope},
+    error::ParseError,
+    tables::{
+        glyf::{CompositeGlyphScale, EmptyGlyph, Glyph},
+        loca::LocaTable,
+        FontTableProvider, HeadTable,
+    },
+    tag,
+};
+use std::{collections::BTreeMap, sync::Arc};
+
+/// Composite glyphs can refer to other composite glyphs, up to this depth
+const MAX_COMPONENT_DEPTH: u8 = 8;
+
+/// A point in font units, with y pointing up
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+impl Point {
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    /// Return the point halfway between this one and another
+    fn midpoint(self, other: Self) -> Self {
+        Self::new((self.x + other.x) / 2.0, (self.y + other.y) / 2.0)
+    }
+}
+impl std::ops::Add for Point {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+/// A single drawing command in an [`IconPath`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathCommand {
+    /// Start a new contour at a point
+    MoveTo(Point),
+
+    /// Draw a straight line to a point
+    LineTo(Point),
+
+    /// Draw a quadratic curve to a point - given as the control point, followed by the end point
+    QuadTo(Point, Point),
+
+    /// Close the contour, with a straight line back to where it started
+    Close,
+}
+
+/// The outline of a glyph, as returned by [`Font::outline`]  
+/// Coordinates are in font units, with y pointing up from the baseline
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct IconPath {
+    /// The drawing commands, one closed contour after another
+    pub commands: Vec<PathCommand>,
+
+    /// The size of the em square, in font units
+    pub units_per_em: u16,
+}
+impl IconPath {
+    /// Check if the path draws nothing, as with a space
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+}
+
+impl Font<'_> {
+    /// Return the outline of a glyph at a variation  
+    /// The contours are read from the `glyf` table, and moved by the `gvar` deltas for the variation's axes
+    ///
+    /// The `FILL` axis also swaps some glyphs out for their filled versions, as the font's GSUB table does,
+    /// so the outline is the one the font would draw for the glyph at that variation
+    pub fn outline(&self, glyph_id: u16, variation: &IconVariation) -> Result<IconPath, FontError> {
+        Ok(self.varied_outline(glyph_id, variation)?.path)
+    }
+
+    /// Return the outline of a glyph at a variation, along with what is needed to vary its metrics
+    pub(crate) fn varied_outline(
+        &self,
+        glyph_id: u16,
+        variation: &IconVariation,
+    ) -> Result<VariedOutline, FontError> {
+        let varied = self.varied_tables(variation)?;
+        let provider = self.table_provider();
+        if glyph_id >= self.tables.num_glyphs {
+            return Err(ParseError::BadIndex.into());
+        }
+
+        let mut outliner = Outliner {
+            glyf: provider.table(tag::GLYF)?.unwrap_or_default(),
+            glyph_offsets: self.glyph_offsets()?,
+            gvar: None,
+            coordinates: Vec::new(),
+            commands: Vec::new(),
+            phantom_deltas: [Point::default(); 4],
+        };
+
+        let glyph_id = varied
+            .substitutions
+            .get(&glyph_id)
+            .copied()
+            .unwrap_or(glyph_id);
+        if let (Some(tuple), Some(gvar)) = (&varied.tuple, provider.table(tag::GVAR)?) {
+            outliner.gvar = Some(Gvar::read(gvar)?);
+            outliner.coordinates = tuple.iter().map(|&value| f32::from(value)).collect();
+        }
+
+        outliner.draw(glyph_id, Transform::IDENTITY, 0)?;
+        Ok(VariedOutline {
+            glyph_id,
+            path: IconPath {
+                commands: outliner.commands,
+                units_per_em: self.tables.units_per_em,
+            },
+            varied,
+            phantom_deltas: outliner.phantom_deltas,
+        })
+    }
+
+    /// Return where each glyph starts and ends in the `glyf` table, from the `loca` table
+    fn glyph_offsets(&self) -> Result<&[u32], FontError> {
+        if let Some(offsets) = self.tables.glyph_offsets.get() {
+            return Ok(offsets);
+        }
+
+        let provider = self.table_provider();
+        let head = provider.read_table_data(tag::HEAD)?;
+        let head = ReadScope::new(&head).read::<HeadTable>()?;
+        let loca = provider.read_table_data(tag::LOCA)?;
+        let loca = ReadScope::new(&loca).read_dep::<LocaTable<'_>>((
+            usize::from(self.tables.num_glyphs),
+            head.index_to_loc_format,
+        ))?;
+        let offsets = loca.offsets.iter().collect::<Vec<_>>();
+        Ok(self.tables.glyph_offsets.get_or_init(|| offsets))
+    }
+
+    /// Return the outline of the glyph for a codepoint, failing if the font does not have one
+    pub(crate) fn icon_outline(
+        &self,
+        codepoint: u32,
+        variation: &IconVariation,
+    ) -> Result<IconPath, FontError> {
+        match self.index_of(codepoint) {
+            Some(id) if id != 0 => self.outline(id, variation),
+            _ => Err(FontError::MissingGlyph(codepoint)),
+        }
+    }
+}
+
+/// The outline of a glyph at a variation, as drawn by [`Font::varied_outline`]
+pub(crate) struct VariedOutline {
+    /// The glyph that was drawn, which the variation may have swapped for another
+    pub glyph_id: u16,
+
+    pub path: IconPath,
+
+    /// The variation the glyph was drawn at
+    pub varied: Arc<VariedTables>,
+
+    /// How far the variation moves each of the glyph's phantom points - its origin, its advance,
+    /// and the top and bottom of its vertical metrics
+    pub phantom_deltas: [Point; 4],
+}
+
+/// Walks the glyphs of a font, collecting the commands that draw them
+struct Outliner<'a> {
+    glyf: &'a [u8],
+
+    /// The offset of each glyph in the `glyf` table, and of its end
+    glyph_offsets: &'a [u32],
+
+    gvar: Option<Gvar<'a>>,
+
+    /// The normalized value of each axis, in `fvar` order
+    coordinates: Vec<f32>,
+
+    commands: Vec<PathCommand>,
+
+    /// The deltas for the phantom points of the outermost glyph
+    phantom_deltas: [Point; 4],
+}
+impl<'a> Outliner<'a> {
+    /// Parse a glyph from the `glyf` table
+    fn glyph(&self, glyph_id: u16) -> Result<Glyph<'a>, ParseError> {
+        let index = usize::from(glyph_id);
+        let (Some(&start), Some(&end)) = (
+            self.glyph_offsets.get(index),
+            self.glyph_offsets.get(index + 1),
+        ) else {
+            return Err(ParseError::BadIndex);
+        };
+        if end <= start {
+            return Ok(Glyph::Empty(EmptyGlyph::new()));
+        }
+
+        let data = self.glyf.get(start as usize..end as usize);
+        ReadScope::new(data.ok_or(ParseError::BadEof)?).read::<Glyph<'_>>()
+    }
+
+    /// Draw a glyph, and any components it is made of
+    fn draw(&mut self, glyph_id: u16, transform: Transform, depth: u8) -> Result<(), ParseError> {
+        if depth > MAX_COMPONENT_DEPTH {
+            return Err(ParseError::LimitExceeded);
+        }
+
+        match self.glyph(glyph_id)? {
+            Glyph::Empty(_) => {}
+
+            Glyph::Simple(glyph) => {
+                let mut points = glyph
+                    .coordinates
+                    .iter()
+                    .map(|(_, point)| Point::new(f32::from(point.0), f32::from(point.1)))
+                    .collect::<Vec<_>>();
+                if let Some(gvar) = &self.gvar {
+                    let deltas = gvar.deltas(
+                        glyph_id,
+                        &self.coordinates,
+                        &points,
+                        &glyph.end_pts_of_contours,
+                    )?;
+                    self.keep_phantom_deltas(&deltas[points.len()..], depth);
+                    for (point, delta) in points.iter_mut().zip(deltas) {
+                        *point = *point + delta;
+                    }
+                }
+
+                let mut start = 0;
+                for &end in &glyph.end_pts_of_contours {
+                    let end = usize::from(end) + 1;
+                    let contour = glyph
+                        .coordinates
+                        .get(start..end)
+                        .ok_or(ParseError::BadIndex)?
+                        .iter()
+                        .zip(&points[start..end])
+                        .map(|((flag, _), &point)| (flag.is_on_curve(), transform.apply(point)))
+                        .collect::<Vec<_>>();
+                    self.contour(&contour);
+                    start = end;
+                }
+            }
+
+            Glyph::Composite(glyph) => {
+                let offsets = glyph
+                    .glyphs
+                    .iter()
+                    .map(|component| {
+                        let x = i32::from(component.argument1) as f32;
+                        let y = i32::from(component.argument2) as f32;
+                        Point::new(x, y)
+                    })
+                    .collect::<Vec<_>>();
+                let deltas = match &self.gvar {
+                    Some(gvar) => gvar.deltas(glyph_id, &self.coordinates, &offsets, &[])?,
+                    None => vec![Point::default(); offsets.len() + 4],
+                };
+                self.keep_phantom_deltas(&deltas[offsets.len()..], depth);
+
+                for ((component, offset), delta) in glyph.glyphs.iter().zip(offsets).zip(deltas) {
+                    // Components positioned by matching points are not used by these fonts
+                    let offset = match component.flags.args_are_xy_values() {
+                        true => offset + delta,
+                        false => Point::default(),
+                    };
+
+                    let local = Transform::new(component.scale, offset);
+                    self.draw(component.glyph_index, transform.compose(&local), depth + 1)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Store the deltas for the phantom points of a glyph, if it is the one being drawn and not a component
+    fn keep_phantom_deltas(&mut self, deltas: &[Point], depth: u8) {
+        if depth == 0 {
+            for (phantom, delta) in self.phantom_deltas.iter_mut().zip(deltas) {
+                *phantom = *delta;
+            }
+        }
+    }
+
+    /// Draw a single contour, given as its points and whether each one is on the curve  
+    /// Two control points in a row have an implied point on the curve halfway between them
+    fn contour(&mut self, points: &[(bool, Point)]) {
+        let (Some(&(_, first)), Some(&(_, last))) = (points.first(), points.last()) else {
+            return;
+        };
+
+        // Start from the first point on the curve, or between the ends if there is none
+        let (origin, skip, remaining) = match points.iter().position(|&(on_curve, _)| on_curve) {
+            Some(i) => (points[i].1, i + 1, points.len() - 1),
+            None => (last.midpoint(first), 0, points.len()),
+        };
+        self.commands.push(PathCommand::MoveTo(origin));
+
+        let mut control = None;
+        for (on_curve, point) in points.iter().cycle().skip(skip).take(remaining) {
+            match (on_curve, control.take()) {
+                (true, None) => self.commands.push(PathCommand::LineTo(*point)),
+                (true, Some(control)) => self.commands.push(PathCommand::QuadTo(control, *point)),
+                (false, None) => control = Some(*point),
+                (false, Some(previous)) => {
+                    let implied = previous.midpoint(*point);
+                    self.commands.push(PathCommand::QuadTo(previous, implied));
+                    control = Some(*point);
+                }
+            }
+        }
+
+        if let Some(control) = control {
+            self.commands.push(PathCommand::QuadTo(control, origin));
+        }
+        self.commands.push(PathCommand::Close);
+    }
+}
+
+/// An affine transform, positioning the components of a composite glyph
+#[derive(Debug, Clone, Copy)]
+struct Transform {
+    xx: f32,
+    xy: f32,
+    yx: f32,
+    yy: f32,
+    offset: Point,
+}
+impl Transform {
+    const IDENTITY: Self = Self {
+        xx: 1.0,
+        xy: 0.0,
+        yx: 0.0,
+        yy: 1.0,
+        offset: Point::new(0.0, 0.0),
+    };
+
+    /// Build the transform of a component, scaled and then moved by its offset
+    fn new(scale: Option<CompositeGlyphScale>, offset: Point) -> Self {
+        let (xx, xy, yx, yy) = match scale {
+            None => (1.0, 0.0, 0.0, 1.0),
+            Some(CompositeGlyphScale::Scale(scale)) => {
+                let scale = f32::from(scale);
+                (scale, 0.0, 0.0, scale)
+            }
+            Some(CompositeGlyphScale::XY { x_scale, y_scale }) => {
+                (f32::from(x_scale), 0.0, 0.0, f32::from(y_scale))
+            }
+            Some(CompositeGlyphScale::Matrix([[xx, xy], [yx, yy]])) => {
+                (f32::from(xx), f32::from(xy), f32::from(yx), f32::from(yy))
+            }
+        };
+        Self {
+            xx,
+            xy,
+            yx,
+            yy,
+            offset,
+        }
+    }
+
+    fn apply(&self, point: Point) -> Point {
+        Point::new(
+            self.xx * point.x + self.yx * point.y + self.offset.x,
+            self.xy * point.x + self.yy * point.y + self.offset.y,
+        )
+    }
+
+    /// Return the transform that applies `inner`, and then this one
+    fn compose(&self, inner: &Self) -> Self {
+        Self {
+            xx: self.xx * inner.xx + self.yx * inner.xy,
+            xy: self.xy * inner.xx + self.yy * inner.xy,
+            yx: self.xx * inner.yx + self.yx * inner.yy,
+            yy: self.xy * inner.yx + self.yy * inner.yy,
+            offset: self.apply(inner.offset),
+        }
+    }
+}
+
+/// The glyph variation table, which moves the points of each glyph across the font's axes
+///
+/// `allsorts` can read the table, but keeps the regions each set of deltas applies to private,
+/// so the parts needed to vary a single glyph are read here
+struct Gvar<'a> {
+    scope: ReadScope<'a>,
+    axis_count: usize,
+    shared_tuples_offset: usize,
+    glyph_count: u16,
+    long_offsets: bool,
+    data_offset: usize,
+}
+impl<'a> Gvar<'a> {
+    /// Flag on the tuple count - the glyph's deltas share a set of point numbers
+    const SHARED_POINT_NUMBERS: u16 = 0x8000;
+
+    /// Flag on a tuple index - the header has its own peak tuple
+    const EMBEDDED_PEAK_TUPLE: u16 = 0x8000;
+
+    /// Flag on a tuple index - the header has the start and end of its region
+    const INTERMEDIATE_REGION: u16 = 0x4000;
+
+    /// Flag on a tuple index - the deltas have their own point numbers
+    const PRIVATE_POINT_NUMBERS: u16 = 0x2000;
+
+    fn read(data: &'a [u8]) -> Result<Self, ParseError> {
+        let scope = ReadScope::new(data);
+        let mut ctxt = scope.ctxt();
+        let _version = ctxt.read_u32be()?;
+        let axis_count = usize::from(ctxt.read_u16be()?);
+        let _shared_tuple_count = ctxt.read_u16be()?;
+        let shared_tuples_offset = ctxt.read_u32be()? as usize;
+        let glyph_count = ctxt.read_u16be()?;
+        let flags = ctxt.read_u16be()?;
+        let data_offset = ctxt.read_u32be()? as usize;
+
+        Ok(Self {
+            scope,
+            axis_count,
+            shared_tuples_offset,
+            glyph_count,
+            long_offsets: flags & 1 == 1,
+            data_offset,
+        })
+    }
+
+    /// Return the variation data for a glyph, if it has any
+    fn glyph_data(&self, glyph_id: u16) -> Result<Option<ReadScope<'a>>, ParseError> {
+        if glyph_id >= self.glyph_count {
+            return Ok(None);
+        }
+
+        // The offsets start after the 20-byte header
+        let index = usize::from(glyph_id);
+        let (start, end) = match self.long_offsets {
+            true => {
+                let mut ctxt = self.scope.offset(20 + 4 * index).ctxt();
+                (ctxt.read_u32be()? as usize, ctxt.read_u32be()? as usize)
+            }
+            false => {
+                let mut ctxt = self.scope.offset(20 + 2 * index).ctxt();
+                let start = 2 * usize::from(ctxt.read_u16be()?);
+                (start, 2 * usize::from(ctxt.read_u16be()?))
+            }
+        };
+
+        match end.checked_sub(start) {
+            Some(0) => Ok(None),
+            Some(length) => Ok(Some(
+                self.scope.offset_length(self.data_offset + start, length)?,
+            )),
+            None => Err(ParseError::BadOffset),
+        }
+    }
+
+    /// Read a tuple of normalized coordinates
+    fn read_tuple(&self, ctxt: &mut ReadCtxt<'_>) -> Result<Vec<f32>, ParseError> {
+        (0..self.axis_count)
+            .map(|_| Ok(f32::from(ctxt.read_i16be()?) / 16384.0))
+            .collect()
+    }
+
+    /// Return the sum of the deltas for each point of a glyph, at normalized axis coordinates  
+    /// Points are those of a simple glyph, or the offsets of a composite glyph's components,
+    /// and are followed by the deltas for the glyph's 4 phantom points
+    ///
+    /// Deltas for the points of a simple glyph that are left out are inferred from their neighbours,
+    /// contour by contour - composite glyphs have no contours, so those points are not moved
+    fn deltas(
+        &self,
+        glyph_id: u16,
+        coordinates: &[f32],
+        points: &[Point],
+        contours: &[u16],
+    ) -> Result<Vec<Point>, ParseError> {
+        // Each glyph also has 4 phantom points, for its metrics
+        let num_points = points.len() + 4;
+        let mut deltas = vec![Point::default(); num_points];
+        let Some(scope) = self.glyph_data(glyph_id)? else {
+            return Ok(deltas);
+        };
+
+        let mut headers = scope.ctxt();
+        let count = headers.read_u16be()?;
+        let data_offset = usize::from(headers.read_u16be()?);
+        let mut data = scope.offset(data_offset).ctxt();
+        let shared_points = match count & Self::SHARED_POINT_NUMBERS {
+            0 => None,
+            _ => Some(read_point_numbers(&mut data)?),
+        };
+
+        for _ in 0..count & 0x0FFF {
+            let size = usize::from(headers.read_u16be()?);
+            let index = headers.read_u16be()?;
+            let peak = match index & Self::EMBEDDED_PEAK_TUPLE {
+                0 => {
+                    let offset = self.shared_tuples_offset
+                        + 2 * self.axis_count * usize::from(index & 0x0FFF);
+                    self.read_tuple(&mut self.scope.offset(offset).ctxt())?
+                }
+                _ => self.read_tuple(&mut headers)?,
+            };
+            let region = match index & Self::INTERMEDIATE_REGION {
+                0 => None,
+                _ => Some((
+                    self.read_tuple(&mut headers)?,
+                    self.read_tuple(&mut headers)?,
+                )),
+            };
+
+            let mut tuple_data = ReadScope::new(data.read_slice(size)?).ctxt();
+            let scalar = region_scalar(coordinates, &peak, region.as_ref());
+            if scalar == 0.0 {
+                continue;
+            }
+
+            let point_numbers = match index & Self::PRIVATE_POINT_NUMBERS {
+                0 => shared_points.clone().ok_or(ParseError::MissingValue)?,
+                _ => read_point_numbers(&mut tuple_data)?,
+            };
+            let point_numbers = point_numbers
+                .unwrap_or_else(|| (0..num_points).map(|n| n as u16).collect::<Vec<_>>());
+            let x_deltas = read_packed_deltas(&mut tuple_data, point_numbers.len())?;
+            let y_deltas = read_packed_deltas(&mut tuple_data, point_numbers.len())?;
+
+            let mut explicit = point_numbers
+                .iter()
+                .zip(x_deltas.into_iter().zip(y_deltas))
+                .filter(|(&number, _)| usize::from(number) < num_points)
+                .map(|(&number, (x, y))| (usize::from(number), Point::new(x, y)))
+                .collect::<BTreeMap<_, _>>();
+
+            // The phantom points are not part of any contour, so are never inferred
+            let phantoms = explicit.split_off(&points.len());
+            let mut region_deltas = infer_deltas(points, contours, &explicit)?;
+            region_deltas.extend(
+                (points.len()..num_points).map(|i| phantoms.get(&i).copied().unwrap_or_default()),
+            );
+
+            for (delta, region_delta) in deltas.iter_mut().zip(region_deltas) {
+                delta.x += scalar * region_delta.x;
+                delta.y += scalar * region_delta.y;
+            }
+        }
+
+        Ok(deltas)
+    }
+}
+
+/// Read a set of packed point numbers  
+/// Returns None if the deltas apply to every point in the glyph
+fn read_point_numbers(ctxt: &mut ReadCtxt<'_>) -> Result<Option<Vec<u16>>, ParseError> {
+    let first = ctxt.read_u8()?;
+    let count = match first & 0x80 {
+        0 => usize::from(first),
+        _ => usize::from(first & 0x7F) << 8 | usize::from(ctxt.read_u8()?),
+    };
+    if count == 0 {
+        return Ok(None);
+    }
+
+    // Runs of differences from the previous point number, either as bytes or as words
+    let mut numbers = Vec::with_capacity(count);
+    let mut number = 0u16;
+    while numbers.len() < count {
+        let control = ctxt.read_u8()?;
+        for _ in 0..=(control & 0x7F) {
+            let difference = match control & 0x80 {
+                0 => u16::from(ctxt.read_u8()?),
+                _ => ctxt.read_u16be()?,
+            };
+            number = number.wrapping_add(difference);
+            numbers.push(number);
+        }
+    }
+
+    numbers.truncate(count);
+    Ok(Some(numbers))
+}
+
+/// Read a run of packed deltas, each stored as zero, a byte, a word or a long  
+/// Long deltas are only defined by newer versions of the spec, but are read here rather than misread as bytes
+fn read_packed_deltas(ctxt: &mut ReadCtxt<'_>, count: usize) -> Result<Vec<f32>, ParseError> {
+    const DELTAS_ARE_ZERO: u8 = 0x80;
+    const DELTAS_ARE_WORDS: u8 = 0x40;
+    const DELTAS_ARE_LONGS: u8 = DELTAS_ARE_ZERO | DELTAS_ARE_WORDS;
+
+    let mut deltas = Vec::with_capacity(count);
+    while deltas.len() < count {
+        let control = ctxt.read_u8()?;
+        for _ in 0..=(control & 0x3F) {
+            let delta = match control & DELTAS_ARE_LONGS {
+                DELTAS_ARE_ZERO => 0,
+                DELTAS_ARE_WORDS => i32::from(ctxt.read_i16be()?),
+                DELTAS_ARE_LONGS => ctxt.read_i32be()?,
+                _ => i32::from(ctxt.read_i8()?),
+            };
+            deltas.push(delta as f32);
+        }
+    }
+
+    deltas.truncate(count);
+    Ok(deltas)
+}
+
+/// Return how strongly a region of the variation space applies at a set of normalized coordinates  
+/// Regions without an explicit start and end run from zero to their peak on each axis
+fn region_scalar(coordinates: &[f32], peak: &[f32], region: Option<&(Vec<f32>, Vec<f32>)>) -> f32 {
+    let mut scalar = 1.0;
+    for (axis, (&coordinate, &peak)) in coordinates.iter().zip(peak).enumerate() {
+        // Axes with no peak do not affect the region
+        if peak == 0.0 || coordinate == peak {
+            continue;
+        }
+
+        let (start, end) = match region {
+            Some((start, end)) => (start[axis], end[axis]),
+            None => (peak.min(0.0), peak.max(0.0)),
+        };
+        if coordinate <= start || coordinate >= end {
+            return 0.0;
+        }
+
+        scalar *= match coordinate < peak {
+            true => (coordinate - start) / (peak - start),
+            false => (end - coordinate) / (end - peak),
+        };
+    }
+    scalar
+}
+
+/// Fill in the deltas for points that were left out of a set of deltas  
+/// Each missing point is interpolated between the nearest points before and after it on its contour that have deltas,
+/// on each axis separately - contours without any deltas are not moved
+///
+/// Fails if the ends of the contours are out of order
+fn infer_deltas(
+    points: &[Point],
+    contours: &[u16],
+    explicit: &BTreeMap<usize, Point>,
+) -> Result<Vec<Point>, ParseError> {
+    let mut deltas = vec![Point::default(); points.len()];
+    for (&number, &delta) in explicit {
+        deltas[number] = delta;
+    }
+    if explicit.len() == points.len() {
+        return Ok(deltas);
+    }
+
+    let mut start = 0;
+    for &end in contours {
+        let end = usize::from(end).min(points.len().saturating_sub(1));
+        if end < start {
+            return Err(ParseError::BadValue);
+        }
+
+        let contour = start..=end;
+        start = end + 1;
+
+        let known = explicit
+            .range(contour.clone())
+            .map(|(&i, _)| i)
+            .collect::<Vec<_>>();
+        if known.is_empty() {
+            continue;
+        }
+
+        for i in contour.filter(|i| !explicit.contains_key(i)) {
+            // The nearest known points either side, wrapping around the contour
+            let next = known.iter().find(|&&k| k > i).or(known.first());
+            let previous = known.iter().rev().find(|&&k| k < i).or(known.last());
+            let (Some(&previous), Some(&next)) = (previous, next) else {
+                continue;
+            };
+
+            deltas[i] = Point::new(
+                interpolate(
+                    points[i].x,
+                    (points[previous].x, deltas[previous].x),
+                    (points[next].x, deltas[next].x),
+                ),
+                interpolate(
+                    points[i].y,
+                    (points[previous].y, deltas[previous].y),
+                    (points[next].y, deltas[next].y),
+                ),
+            );
+        }
+    }
+
+    Ok(deltas)
+}
+
+/// Infer the delta for a coordinate from two reference points, each given as its coordinate and delta  
+/// Between the references the delta is interpolated, and outside them it matches the nearest one
+fn interpolate(coordinate: f32, a: (f32, f32), b: (f32, f32)) -> f32 {
+    let ((low, low_delta), (high, high_delta)) = match a.0 <= b.0 {
+        true => (a, b),
+        false => (b, a),
+    };
+
+    if low == high {
+        match low_delta == high_delta {
+            true => low_delta,
+            false => 0.0,
+        }
+    } else if coordinate <= low {
+        low_delta
+    } else if coordinate >= high {
+        high_delta
+    } else {
+        let t = (coordinate - low) / (high - low);
+        low_delta + t * (high_delta - low_delta)
+    }
+}
+
+#[cfg(all(test, feature = "sharp"))]
+mod tests {
+    use super::*;
+    #[cfg(not(icons_subset))]
+    use crate::IconName as Icon;
+
+    /// Return the points of each contour in a path
+    #[cfg(not(icons_subset))]
+    fn contours(path: &IconPath) -> Vec<Vec<Point>> {
+        let mut contours = Vec::new();
+        for command in &path.commands {
+            match command {
+                PathCommand::MoveTo(point) => contours.push(vec![*point]),
+                PathCommand::LineTo(point) => contours.last_mut().unwrap().push(*point),
+                PathCommand::QuadTo(control, point) => {
+                    contours.last_mut().unwrap().extend([*control, *point])
+                }
+                PathCommand::Close => {}
+            }
+        }
+        contours
+    }
+
+    /// Check that two paths match, to within rounding
+    #[cfg(not(icons_subset))]
+    fn assert_close(a: &IconPath, b: &IconPath) {
+        let (a, b) = (contours(a), contours(b));
+        assert_eq!(a.len(), b.len());
+        for (a, b) in a.iter().zip(&b) {
+            assert_eq!(a.len(), b.len());
+            for (a, b) in a.iter().zip(b) {
+                assert!(
+                    (a.x - b.x).abs() <= 1.0 && (a.y - b.y).abs() <= 1.0,
+                    "{a:?} != {b:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(icons_subset))]
+    fn test_outline() {
+        let font = Font::new_sharp().unwrap();
+        let add = font.index_of(Icon::Add as u32).unwrap();
+        let path = font.outline(add, &IconVariation::DEFAULT).unwrap();
+        assert!(!path.is_empty());
+        assert!(matches!(
+            path.commands.first(),
+            Some(PathCommand::MoveTo(_))
+        ));
+        assert_eq!(path.commands.last(), Some(&PathCommand::Close));
+
+        // Every point lies within the em square, which sits on the descender
+        let em = f32::from(path.units_per_em);
+        for point in contours(&path).concat() {
+            assert!((0.0..=em).contains(&point.x), "{point:?}");
+            assert!((-em..=em).contains(&point.y), "{point:?}");
+        }
+
+        let bold = font.outline(add, &IconVariation::BOLD).unwrap();
+        assert_ne!(bold, path);
+
+        let space = font.index_of(' ' as u32).unwrap();
+        assert!(font
+            .outline(space, &IconVariation::DEFAULT)
+            .unwrap()
+            .is_empty());
+
+        let out_of_range = IconVariation::DEFAULT.with_weight(900.0);
+        assert!(font.outline(add, &out_of_range).is_err());
+        assert!(font.outline(u16::MAX, &IconVariation::DEFAULT).is_err());
+    }
+
+    #[test]
+    #[cfg(not(icons_subset))]
+    fn test_outline_matches_instance() {
+        let font = Font::new_sharp().unwrap();
+        let icons = [
+            Icon::Add,
+            Icon::Delete,
+            Icon::Home,
+            Icon::Settings,
+            Icon::_10k,
+        ];
+        let variations = [
+            IconVariation::DEFAULT,
+            IconVariation::FILLED_BOLD,
+            IconVariation::DEFAULT
+                .with_weight(250.0)
+                .with_grade(-25.0)
+                .with_optical_size(40.0),
+        ];
+
+        // The static instances are varied by allsorts, with the filled glyphs swapped in
+        for variation in variations {
+            let data = font.instance(&variation, "Instance").unwrap();
+            let instance = Font::new(&data).unwrap();
+            for icon in icons {
+                let id = font.index_of(icon as u32).unwrap();
+                let varied = font.outline(id, &variation).unwrap();
+                let baked = instance.outline(id, &IconVariation::DEFAULT).unwrap();
+                assert_close(&varied, &baked);
+            }
+        }
+    }
+
+    #[test]
+    fn test_contour() {
+        let mut outliner = Outliner {
+            glyf: &[],
+            glyph_offsets: &[],
+            gvar: None,
+            coordinates: Vec::new(),
+            commands: Vec::new(),
+            phantom_deltas: [Point::default(); 4],
+        };
+
+        // Only control points, with implied points on the curve between each of them
+        let p = |x, y| Point::new(x, y);
+        outliner.contour(&[
+            (false, p(0.0, 0.0)),
+            (false, p(2.0, 0.0)),
+            (false, p(2.0, 2.0)),
+        ]);
+        assert_eq!(
+            outliner.commands,
+            vec![
+                PathCommand::MoveTo(p(1.0, 1.0)),
+                PathCommand::QuadTo(p(0.0, 0.0), p(1.0, 0.0)),
+                PathCommand::QuadTo(p(2.0, 0.0), p(2.0, 1.0)),
+                PathCommand::QuadTo(p(2.0, 2.0), p(1.0, 1.0)),
+                PathCommand::Close,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_packed_deltas() {
+        let data = [
+            0x81, // 2 zeros
+            0x01, 0x05, 0xFB, // 2 bytes: 5, -5
+            0x40, 0x01, 0x00, // 1 word: 256
+            0xC1, 0x00, 0x01, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFE, // 2 longs: 65536, -2
+            0x00, 0x07, // 1 byte: 7
+        ];
+        let mut ctxt = ReadScope::new(&data).ctxt();
+        let deltas = read_packed_deltas(&mut ctxt, 8).unwrap();
+        assert_eq!(deltas, [0.0, 0.0, 5.0, -5.0, 256.0, 65536.0, -2.0, 7.0]);
+
+        // Runs that go past the count are cut short
+        let mut ctxt = ReadScope::new(&data).ctxt();
+        assert_eq!(read_packed_deltas(&mut ctxt, 1).unwrap(), [0.0]);
+
+        // The data ends partway through the longs
+        let mut ctxt = ReadScope::new(&data[..10]).ctxt();
+        assert!(read_packed_deltas(&mut ctxt, 8).is_err());
+    }
+
+    #[test]
+    fn test_interpolate() {
+        assert_eq!(interpolate(5.0, (0.0, 10.0), (10.0, 20.0)), 15.0);
+        assert_eq!(interpolate(5.0, (10.0, 20.0), (0.0, 10.0)), 15.0);
+        assert_eq!(interpolate(-5.0, (0.0, 10.0), (10.0, 20.0)), 10.0);
+        assert_eq!(interpolate(15.0, (0.0, 10.0), (10.0, 20.0)), 20.0);
+        assert_eq!(interpolate(5.0, (0.0, 10.0), (0.0, 20.0)), 0.0);
+        assert_eq!(interpolate(5.0, (0.0, 10.0), (0.0, 10.0)), 10.0);
+    }
+
+    #[test]
+    fn test_infer_deltas() {
+        let points = (0..6)
+            .map(|i| Point::new(i as f32, 0.0))
+            .collect::<Vec<_>>();
+        let explicit = BTreeMap::from([(0, Point::new(1.0, 0.0)), (2, Point::new(3.0, 0.0))]);
+
+        // The point between the two known ones is interpolated, and the other contour is not moved
+        let deltas = infer_deltas(&points, &[2, 5], &explicit).unwrap();
+        assert_eq!(deltas[1], Point::new(2.0, 0.0));
+        assert_eq!(deltas[4], Point::default());
+
+        // Contours that end before they start are rejected, rather than panicking
+        assert!(matches!(
+            infer_deltas(&points, &[5, 2], &explicit),
+            Err(ParseError::BadValue)
+        ));
+    }
+}
@@ -0,0 +1,502 @@
+//! Software rendering
+//!
+//! Rasterizes icon outlines into anti-aliased pixel buffers on the CPU, with no GPU or system libraries
+//!
+use super::{Font, FontError, IconPath, PathCommand, Point};
+use crate::IconVariation;
+
+#[cfg(feature = "png")]
+use std::borrow::Cow;
+
+/// The layout of the pixels in an [`IconPixmap`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PixelFormat {
+    /// One byte per pixel - how much of the pixel the icon covers, from 0 to 255
+    Alpha8,
+
+    /// Four bytes per pixel - red, green, blue and alpha, with the color premultiplied by the alpha
+    Rgba8,
+}
+impl PixelFormat {
+    /// Return the number of bytes used for each pixel
+    pub const fn bytes_per_pixel(self) -> usize {
+        match self {
+            Self::Alpha8 => 1,
+            Self::Rgba8 => 4,
+        }
+    }
+}
+
+/// A rendered icon, stored row by row from the top left
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IconPixmap {
+    pub width: u32,
+    pub height: u32,
+    pub format: PixelFormat,
+    pub data: Vec<u8>,
+}
+impl IconPixmap {
+    /// Return the bytes of a single pixel, or None if it is outside the image
+    pub fn pixel(&self, x: u32, y: u32) -> Option<&[u8]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let size = self.format.bytes_per_pixel();
+        let start = (y as usize * self.width as usize + x as usize) * size;
+        self.data.get(start..start + size)
+    }
+
+    /// Color a coverage mask, returning a premultiplied RGBA image
+    /// The color is given as straight (not premultiplied) red, green, blue and alpha
+    ///
+    /// Images that are already RGBA are returned unchanged
+    pub fn with_color(&self, color: [u8; 4]) -> Self {
+        if self.format == PixelFormat::Rgba8 {
+            return self.clone();
+        }
+
+        let [r, g, b, a] = color.map(u32::from);
+        let data = self
+            .data
+            .iter()
+            .flat_map(|&coverage| {
+                let alpha = a * u32::from(coverage);
+                [r, g, b, 255]
+                    .map(|channel| ((channel * alpha + 255 * 255 / 2) / (255 * 255)) as u8)
+            })
+            .collect();
+
+        Self {
+            width: self.width,
+            height: self.height,
+            format: PixelFormat::Rgba8,
+            data,
+        }
+    }
+
+    /// Encode the image as a PNG
+    /// Coverage masks are written in grayscale, and RGBA images with straight alpha, as PNG expects
+    #[cfg(feature = "png")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "png")))]
+    pub fn to_png(&self) -> Result<Box<[u8]>, FontError> {
+        let (color_type, data) = match self.format {
+            PixelFormat::Alpha8 => (
+                png::ColorType::Grayscale,
+                Cow::Borrowed(self.data.as_slice()),
+            ),
+            PixelFormat::Rgba8 => (png::ColorType::Rgba, Cow::Owned(self.unpremultiply())),
+        };
+
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, self.width, self.height);
+        encoder.set_color(color_type);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&data)?;
+        writer.finish()?;
+
+        Ok(png.into_boxed_slice())
+    }
+
+    /// Divide each color channel of an RGBA image by its alpha
+    #[cfg(feature = "png")]
+    fn unpremultiply(&self) -> Vec<u8> {
+        self.data
+            .chunks_exact(4)
+            .flat_map(|pixel| {
+                let alpha = u32::from(pixel[3]);
+                let channel = |c: u8| match alpha {
+                    0 => 0,
+                    _ => ((u32::from(c) * 255 + alpha / 2) / alpha).min(255) as u8,
+                };
+                [
+                    channel(pixel[0]),
+                    channel(pixel[1]),
+                    channel(pixel[2]),
+                    pixel[3],
+                ]
+            })
+            .collect()
+    }
+}
+
+impl Font<'_> {
+    /// Render an icon as an anti-aliased coverage mask, `size` pixels across
+    /// Accepts an icon, a char or a raw codepoint
+    ///
+    /// The image covers the font's em square, so icons keep the margins they have in the font
+    pub fn render_mask(
+        &self,
+        icon: impl Into<u32>,
+        size: u32,
+        variation: &IconVariation,
+    ) -> Result<IconPixmap, FontError> {
+        let path = self.icon_outline(icon.into(), variation)?;
+        Ok(path.rasterize(size))
+    }
+
+    /// Render an icon in a color, as a premultiplied RGBA image `size` pixels across
+    /// The color is given as straight (not premultiplied) red, green, blue and alpha
+    pub fn render_rgba(
+        &self,
+        icon: impl Into<u32>,
+        size: u32,
+        color: [u8; 4],
+        variation: &IconVariation,
+    ) -> Result<IconPixmap, FontError> {
+        let mask = self.render_mask(icon, size, variation)?;
+        Ok(mask.with_color(color))
+    }
+
+    /// Render an icon in a color, and encode it as a PNG `size` pixels across
+    /// Returns a file extension and the image data, like [`super::BitmapExt::export`]
+    #[cfg(feature = "png")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "png")))]
+    pub fn render_png(
+        &self,
+        icon: impl Into<u32>,
+        size: u32,
+        color: [u8; 4],
+        variation: &IconVariation,
+    ) -> Result<(&'static str, Box<[u8]>), FontError> {
+        let image = self.render_rgba(icon, size, color, variation)?;
+        Ok(("png", image.to_png()?))
+    }
+}
+
+impl IconPath {
+    /// Rasterize the path into a coverage mask, `size` pixels across
+    /// The em square is scaled to fit the image, and anything outside it is clipped
+    ///
+    /// Overlapping contours are filled using the non-zero winding rule
+    pub fn rasterize(&self, size: u32) -> IconPixmap {
+        let scale = match self.units_per_em {
+            0 => 0.0,
+            em => size as f32 / f32::from(em),
+        };
+        let em = f32::from(self.units_per_em);
+        let to_pixels = |p: Point| Point::new(p.x * scale, (em - p.y) * scale);
+
+        let mut rasterizer = Rasterizer::new(size as usize, size as usize);
+        let mut start = Point::default();
+        let mut current = Point::default();
+        for command in &self.commands {
+            match *command {
+                PathCommand::MoveTo(to) => {
+                    rasterizer.line(current, start);
+                    start = to_pixels(to);
+                    current = start;
+                }
+                PathCommand::LineTo(to) => {
+                    let to = to_pixels(to);
+                    rasterizer.line(current, to);
+                    current = to;
+                }
+                PathCommand::QuadTo(control, to) => {
+                    let to = to_pixels(to);
+                    rasterizer.quad(current, to_pixels(control), to);
+                    current = to;
+                }
+                PathCommand::Close => {
+                    rasterizer.line(current, start);
+                    current = start;
+                }
+            }
+        }
+        rasterizer.line(current, start);
+
+        IconPixmap {
+            width: size,
+            height: size,
+            format: PixelFormat::Alpha8,
+            data: rasterizer.coverage(),
+        }
+    }
+}
+
+/// Accumulates the signed area each edge covers in each pixel
+///
+/// Summing the accumulated values along a row gives the winding of each pixel, weighted by how much of it
+/// is covered - the absolute value, clamped to 1, is the coverage under the non-zero rule
+struct Rasterizer {
+    width: usize,
+    height: usize,
+
+    /// One value per pixel, with two spare columns per row for edges that end on the right of the image
+    accumulation: Vec<f32>,
+}
+impl Rasterizer {
+    /// How far the edges of a flattened curve can stray from it, in pixels
+    const TOLERANCE: f32 = 0.05;
+
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            accumulation: vec![0.0; (width + 2) * height],
+        }
+    }
+
+    /// Add a straight edge, in pixels
+    fn line(&mut self, from: Point, to: Point) {
+        if from.y == to.y || !(from.y.is_finite() && to.y.is_finite()) {
+            return;
+        }
+
+        // Edges are walked from top to bottom, with the direction kept as the sign of their area
+        let (direction, top, bottom) = match from.y < to.y {
+            true => (1.0, from, to),
+            false => (-1.0, to, from),
+        };
+        let dxdy = (bottom.x - top.x) / (bottom.y - top.y);
+
+        let stride = self.width + 2;
+        let max_x = self.width as f32;
+        let first_row = top.y.max(0.0) as usize;
+        let last_row = (bottom.y.ceil().max(0.0) as usize).min(self.height);
+        let mut x = top.x + ((first_row as f32).max(top.y) - top.y) * dxdy;
+        for row in first_row..last_row {
+            let y = row as f32;
+            let dy = (y + 1.0).min(bottom.y) - y.max(top.y);
+            let next_x = x + dxdy * dy;
+            let area = dy * direction;
+
+            // Anything left of the image still counts towards the pixels to its right
+            let (x0, x1) = match x < next_x {
+                true => (x, next_x),
+                false => (next_x, x),
+            };
+            let (x0, x1) = (x0.clamp(0.0, max_x), x1.clamp(0.0, max_x));
+            let accumulation = &mut self.accumulation[row * stride..(row + 1) * stride];
+            Self::span(accumulation, x0, x1, area);
+
+            x = next_x;
+        }
+    }
+
+    /// Spread the area an edge covers in one row across the pixels it crosses, from `x0` to `x1`
+    fn span(row: &mut [f32], x0: f32, x1: f32, area: f32) {
+        let x0_floor = x0.floor();
+        let x1_ceil = x1.ceil();
+        let (start, end) = (x0_floor as usize, x1_ceil as usize);
+
+        if end <= start + 1 {
+            // The edge stays within one pixel - split the area by how far across it the edge is
+            let middle = 0.5 * (x0 + x1) - x0_floor;
+            row[start] += area * (1.0 - middle);
+            row[start + 1] += area * middle;
+            return;
+        }
+
+        // The edge crosses several pixels - the first and last are partly covered,
+        // and those between get an equal share
+        let slope = (x1 - x0).recip();
+        let x0_fraction = x0 - x0_floor;
+        let first = 0.5 * slope * (1.0 - x0_fraction).powi(2);
+        let x1_fraction = x1 - x1_ceil + 1.0;
+        let last = 0.5 * slope * x1_fraction.powi(2);
+
+        row[start] += area * first;
+        if end == start + 2 {
+            row[start + 1] += area * (1.0 - first - last);
+        } else {
+            let second = slope * (1.5 - x0_fraction);
+            row[start + 1] += area * (second - first);
+            for value in &mut row[start + 2..end - 1] {
+                *value += area * slope;
+            }
+            let before_last = second + (end - start - 3) as f32 * slope;
+            row[end - 1] += area * (1.0 - before_last - last);
+        }
+        row[end] += area * last;
+    }
+
+    /// Add a quadratic curve, in pixels, as a series of straight edges
+    /// Each edge strays at most [`Self::TOLERANCE`] pixels from the curve
+    fn quad(&mut self, from: Point, control: Point, to: Point) {
+        // A curve split into n edges is at most |from - 2 * control + to| / (4 * n^2) from any of them
+        let deviation = (from.x - 2.0 * control.x + to.x).hypot(from.y - 2.0 * control.y + to.y);
+        let segments = ((deviation / (4.0 * Self::TOLERANCE)).sqrt().ceil() as usize).min(256);
+        if segments <= 1 {
+            self.line(from, to);
+            return;
+        }
+
+        let mut previous = from;
+        for i in 1..=segments {
+            let t = i as f32 / segments as f32;
+            let u = 1.0 - t;
+            let point = Point::new(
+                u * u * from.x + 2.0 * u * t * control.x + t * t * to.x,
+                u * u * from.y + 2.0 * u * t * control.y + t * t * to.y,
+            );
+            self.line(previous, point);
+            previous = point;
+        }
+    }
+
+    /// Sum the accumulated area along each row, into the coverage of each pixel
+    fn coverage(&self) -> Vec<u8> {
+        let stride = self.width + 2;
+        let mut coverage = Vec::with_capacity(self.width * self.height);
+        for row in self.accumulation.chunks_exact(stride) {
+            let mut winding = 0.0;
+            for value in &row[..self.width] {
+                winding += value;
+                coverage.push((winding.abs().min(1.0) * 255.0).round() as u8);
+            }
+        }
+        coverage
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A square path, drawn clockwise or anticlockwise
+    fn square(commands: &mut Vec<PathCommand>, min: f32, max: f32, clockwise: bool) {
+        let mut corners = [(min, min), (min, max), (max, max), (max, min)];
+        if !clockwise {
+            corners.reverse();
+        }
+
+        let p = |(x, y)| Point::new(x, y);
+        commands.push(PathCommand::MoveTo(p(corners[0])));
+        for corner in &corners[1..] {
+            commands.push(PathCommand::LineTo(p(*corner)));
+        }
+        commands.push(PathCommand::Close);
+    }
+
+    #[test]
+    fn test_rasterize() {
+        let mut path = IconPath {
+            commands: Vec::new(),
+            units_per_em: 10,
+        };
+        square(&mut path.commands, 2.0, 8.0, true);
+        let mask = path.rasterize(10);
+        assert_eq!(mask.data.len(), 100);
+        assert_eq!(mask.pixel(5, 5), Some(&[255][..]));
+        assert_eq!(mask.pixel(2, 7), Some(&[255][..]));
+        assert_eq!(mask.pixel(1, 5), Some(&[0][..]));
+        assert_eq!(mask.pixel(8, 5), Some(&[0][..]));
+        assert_eq!(mask.pixel(10, 5), None);
+
+        // Edges that cut through pixels are anti-aliased
+        path.commands.clear();
+        square(&mut path.commands, 1.0, 9.0, true);
+        let mask = path.rasterize(5);
+        assert_eq!(mask.pixel(0, 2), Some(&[128][..]));
+        assert_eq!(mask.pixel(2, 2), Some(&[255][..]));
+
+        // Curves are flattened
+        path.commands.clear();
+        path.commands.extend([
+            PathCommand::MoveTo(Point::new(0.0, 0.0)),
+            PathCommand::QuadTo(Point::new(10.0, 0.0), Point::new(10.0, 10.0)),
+            PathCommand::Close,
+        ]);
+        let mask = path.rasterize(100);
+        let covered = mask.data.iter().map(|&c| f32::from(c) / 255.0).sum::<f32>();
+        assert!((covered - 10000.0 / 3.0).abs() < 10.0, "{covered}");
+    }
+
+    #[test]
+    fn test_non_zero() {
+        let mut path = IconPath {
+            commands: Vec::new(),
+            units_per_em: 10,
+        };
+
+        // Overlapping contours in the same direction are filled, not cut out
+        square(&mut path.commands, 0.0, 6.0, true);
+        square(&mut path.commands, 4.0, 10.0, true);
+        let mask = path.rasterize(10);
+        assert_eq!(mask.pixel(5, 5), Some(&[255][..]));
+
+        // A contour running the other way cuts a hole
+        path.commands.clear();
+        square(&mut path.commands, 0.0, 10.0, true);
+        square(&mut path.commands, 3.0, 7.0, false);
+        let mask = path.rasterize(10);
+        assert_eq!(mask.pixel(5, 5), Some(&[0][..]));
+        assert_eq!(mask.pixel(1, 5), Some(&[255][..]));
+    }
+
+    #[test]
+    fn test_with_color() {
+        let mask = IconPixmap {
+            width: 2,
+            height: 1,
+            format: PixelFormat::Alpha8,
+            data: vec![255, 128],
+        };
+        let rgba = mask.with_color([255, 64, 0, 128]);
+        assert_eq!(rgba.format, PixelFormat::Rgba8);
+        assert_eq!(rgba.pixel(0, 0), Some(&[128, 32, 0, 128][..]));
+        assert_eq!(rgba.pixel(1, 0), Some(&[64, 16, 0, 64][..]));
+
+        #[cfg(feature = "png")]
+        assert_eq!(rgba.unpremultiply(), [255, 64, 0, 128, 255, 64, 0, 64]);
+    }
+
+    #[test]
+    #[cfg(feature = "sharp")]
+    #[cfg(not(icons_subset))]
+    fn test_render() {
+        use crate::IconName as Icon;
+
+        let font = Font::new_sharp().unwrap();
+        let mask = font
+            .render_mask(Icon::Add, 24, &IconVariation::DEFAULT)
+            .unwrap();
+        assert_eq!((mask.width, mask.height), (24, 24));
+
+        // The bars of the plus sign are 2px wide, from 5px to 19px
+        assert_eq!(mask.pixel(12, 12), Some(&[255][..]));
+        assert_eq!(mask.pixel(5, 11), Some(&[255][..]));
+        assert_eq!(mask.pixel(12, 5), Some(&[255][..]));
+        assert_eq!(mask.pixel(4, 11), Some(&[0][..]));
+        assert_eq!(mask.pixel(5, 5), Some(&[0][..]));
+
+        let rgba = font
+            .render_rgba(Icon::Add, 24, [0, 0, 255, 255], &IconVariation::BOLD)
+            .unwrap();
+        assert_eq!(rgba.data.len(), 24 * 24 * 4);
+        assert_eq!(rgba.pixel(12, 12), Some(&[0, 0, 255, 255][..]));
+        assert_eq!(rgba.pixel(0, 0), Some(&[0, 0, 0, 0][..]));
+
+        assert!(matches!(
+            font.render_mask(0x10FFFF_u32, 24, &IconVariation::DEFAULT),
+            Err(FontError::MissingGlyph(0x10FFFF))
+        ));
+    }
+
+    #[test]
+    #[cfg(all(feature = "png", feature = "sharp"))]
+    #[cfg(not(icons_subset))]
+    fn test_render_png() {
+        use crate::IconName as Icon;
+
+        let font = Font::new_sharp().unwrap();
+        let (extension, data) = font
+            .render_png(Icon::Add, 24, [255, 0, 0, 128], &IconVariation::DEFAULT)
+            .unwrap();
+        assert_eq!(extension, "png");
+
+        let decoder = png::Decoder::new(&data[..]);
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (24, 24));
+        assert_eq!(info.color_type, png::ColorType::Rgba);
+
+        // PNG stores straight alpha, so the color comes back as it was given
+        let pixel = |x: usize, y: usize| &pixels[(y * 24 + x) * 4..(y * 24 + x) * 4 + 4];
+        assert_eq!(pixel(12, 12), &[255, 0, 0, 128]);
+        assert_eq!(pixel(0, 0), &[0, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,326 @@
+//! Font subsetting
+//!
+//! Cuts a font down to a handful of icons, keeping their codepoints and the ligatures that spell them out
+//!
+//! This file is also included by the build script, which subsets the fonts embedded by the
+//! `subset` feature - so it must only depend on `allsorts`
+//!
+use super::instance::with_tables;
+use allsorts::{
+    binary::read::ReadScope,
+    error::ParseError,
+    layout::{new_layout_cache, LayoutTable, SubstLookup, GSUB},
+    subset::SubsetError,
+    tables::{FontTableProvider, MaxpTable},
+    tag,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Cut a font down to the given glyphs, keeping their codepoints and the ligatures that spell them out  
+/// Glyph 0 and the glyphs the ligatures are made of are always kept as well
+///
+/// The subset is a static font in the default style - the variation tables are not kept
+pub fn subset_font(
+    provider: &impl FontTableProvider,
+    glyph_ids: &BTreeSet<u16>,
+) -> Result<Vec<u8>, SubsetError> {
+    // Glyph 0 is .notdef, and must always be kept
+    let mut glyph_ids = glyph_ids.clone();
+    glyph_ids.insert(0);
+
+    // Keep the ligatures for the requested glyphs, along with the glyphs that spell them out
+    let ligatures = ligature_glyphs(provider)?
+        .into_iter()
+        .filter(|(glyph, _)| glyph_ids.contains(glyph))
+        .collect::<Vec<_>>();
+    for (_, components) in &ligatures {
+        glyph_ids.extend(components);
+    }
+
+    let glyph_ids = glyph_ids.into_iter().collect::<Vec<_>>();
+    let data = allsorts::subset::subset(provider, &glyph_ids)?;
+
+    // The subsetter renumbers the glyphs in the order they were given
+    let new_id = |id: &u16| glyph_ids.binary_search(id).map_or(0, |i| i as u16);
+    let ligatures = ligatures
+        .iter()
+        .map(|(glyph, components)| (new_id(glyph), components.iter().map(new_id).collect()))
+        .collect::<Vec<_>>();
+
+    // The subsetter drops GSUB and OS/2, so add them back in
+    let mut tables = vec![(tag::GSUB, ligature_gsub(&ligatures))];
+    if let Some(os2) = provider.table_data(tag::OS_2)? {
+        tables.push((tag::OS_2, os2.into_owned()));
+    }
+
+    Ok(with_tables(&data, tables)?)
+}
+
+/// Return every ligature in a font's GSUB table, as the ligature glyph and the glyphs it is made of  
+/// Only the lookups that the `rlig` and `liga` features apply to Latin text are read, in the order they are applied
+pub fn ligature_glyphs(
+    provider: &impl FontTableProvider,
+) -> Result<Vec<(u16, Vec<u16>)>, ParseError> {
+    let Some(gsub_data) = provider.table_data(tag::GSUB)? else {
+        return Ok(Vec::new());
+    };
+    let maxp = provider.read_table_data(tag::MAXP)?;
+    let num_glyphs = ReadScope::new(&maxp).read::<MaxpTable>()?.num_glyphs;
+
+    let gsub = ReadScope::new(&gsub_data).read::<LayoutTable<GSUB>>()?;
+    let cache = new_layout_cache(gsub);
+    let layout = &cache.layout_table;
+    let Some(lookups) = &layout.opt_lookup_list else {
+        return Ok(Vec::new());
+    };
+
+    let langsys = match layout.find_script_or_default(tag::LATN)? {
+        Some(script) => script.find_langsys_or_default(None)?,
+        None => None,
+    };
+    let mut lookup_indices = BTreeSet::new();
+    for feature in [tag::RLIG, tag::LIGA] {
+        if let Some(feature) = langsys
+            .map(|langsys| layout.find_langsys_feature(langsys, feature, None))
+            .transpose()?
+            .flatten()
+        {
+            lookup_indices.extend(feature.lookup_indices.iter().copied());
+        }
+    }
+
+    let mut ligatures = Vec::new();
+    for index in lookup_indices {
+        let lookup = lookups.lookup_cache_gsub(&cache, usize::from(index))?;
+        let SubstLookup::LigatureSubst(subtables) = &lookup.lookup_subtables else {
+            continue;
+        };
+
+        for subtable in subtables {
+            for first in 0..num_glyphs {
+                let Some(set) = subtable.apply_glyph(first)? else {
+                    continue;
+                };
+
+                for ligature in &set.ligatures {
+                    let mut components = vec![first];
+                    components.extend(&ligature.component_glyphs);
+                    ligatures.push((ligature.ligature_glyph, components));
+                }
+            }
+        }
+    }
+
+    Ok(ligatures)
+}
+
+/// Build a GSUB table with a single required-ligature lookup, like the one in the original fonts
+/// Each ligature is given as the ligature glyph, and the glyphs it is made of
+///
+/// The ligature subtables are wrapped in extension subtables, so the table is not limited to 64KB
+fn ligature_gsub(ligatures: &[(u16, Vec<u16>)]) -> Vec<u8> {
+    const MAX_SUBTABLE_SIZE: usize = u16::MAX as usize - 16;
+
+    // Group the ligatures by their first glyph, as the coverage table requires
+    let mut sets = BTreeMap::<u16, Vec<(u16, &[u16])>>::new();
+    for (glyph, components) in ligatures {
+        if let Some((first, rest)) = components.split_first() {
+            sets.entry(*first).or_default().push((*glyph, rest));
+        }
+    }
+    let sets = sets.into_iter().collect::<Vec<_>>();
+
+    // Split the sets between subtables, each small enough for 16-bit offsets
+    let mut subtables = vec![];
+    let mut start = 0;
+    let mut size = 0;
+    for (i, (_, set)) in sets.iter().enumerate() {
+        let set_size = 8 + set
+            .iter()
+            .map(|(_, rest)| 6 + 2 * rest.len())
+            .sum::<usize>();
+        if size + set_size > MAX_SUBTABLE_SIZE && i > start {
+            subtables.push(ligature_subst(&sets[start..i]));
+            start = i;
+            size = 0;
+        }
+        size += set_size;
+    }
+    if start < sets.len() {
+        subtables.push(ligature_subst(&sets[start..]));
+    }
+
+    let mut gsub = Vec::new();
+
+    // Header - version 1.0, followed by the script, feature and lookup list offsets
+    write_u16s(&mut gsub, &[1, 0, 10, 36, 50]);
+
+    // Script list - DFLT and latn share one script table, with a default language system
+    // that enables feature 0
+    write_u16s(&mut gsub, &[2]);
+    gsub.extend(b"DFLT");
+    write_u16s(&mut gsub, &[14]);
+    gsub.extend(b"latn");
+    write_u16s(&mut gsub, &[14]);
+    write_u16s(&mut gsub, &[4, 0]);
+    write_u16s(&mut gsub, &[0, 0xFFFF, 1, 0]);
+
+    // Feature list - rlig, using lookup 0
+    write_u16s(&mut gsub, &[1]);
+    gsub.extend(b"rlig");
+    write_u16s(&mut gsub, &[8, 0, 1, 0]);
+
+    // Lookup list - a single extension lookup, with one extension subtable per ligature subtable
+    write_u16s(&mut gsub, &[1, 4]);
+    let count = subtables.len() as u16;
+    write_u16s(&mut gsub, &[7, 0, count]);
+    let extensions_start = 6 + 2 * count;
+    for i in 0..count {
+        write_u16s(&mut gsub, &[extensions_start + 8 * i]);
+    }
+
+    let mut offset = 8 * subtables.len();
+    for subtable in &subtables {
+        write_u16s(&mut gsub, &[1, 4]);
+        gsub.extend((offset as u32).to_be_bytes());
+        offset += subtable.len() - 8;
+    }
+    for subtable in subtables {
+        gsub.extend(subtable);
+    }
+
+    gsub
+}
+
+/// A set of ligatures sharing a first glyph, as the first glyph and a list of ligature glyphs
+/// and the rest of the glyphs they are made of
+type LigatureSet<'a> = (u16, Vec<(u16, &'a [u16])>);
+
+/// Build a ligature substitution subtable, from ligature sets keyed by their first glyph
+fn ligature_subst(sets: &[LigatureSet<'_>]) -> Vec<u8> {
+    let count = sets.len() as u16;
+
+    let mut set_data = Vec::new();
+    let mut set_offsets = Vec::new();
+    for (_, ligatures) in sets {
+        set_offsets.push(6 + 2 * count + set_data.len() as u16);
+
+        let start = set_data.len();
+        let ligature_count = ligatures.len() as u16;
+        write_u16s(&mut set_data, &[ligature_count]);
+
+        let mut offset = 2 + 2 * ligature_count;
+        for (_, rest) in ligatures {
+            write_u16s(&mut set_data, &[offset]);
+            offset += 4 + 2 * rest.len() as u16;
+        }
+        for (glyph, rest) in ligatures {
+            write_u16s(&mut set_data, &[*glyph, rest.len() as u16 + 1]);
+            write_u16s(&mut set_data, rest);
+        }
+        debug_assert_eq!(set_data.len() - start, offset as usize);
+    }
+
+    let coverage_offset = 6 + 2 * count + set_data.len() as u16;
+    let mut subst = Vec::new();
+    write_u16s(&mut subst, &[1, coverage_offset, count]);
+    write_u16s(&mut subst, &set_offsets);
+    subst.extend(set_data);
+
+    // Coverage format 1 - the first glyph of each set, in order
+    write_u16s(&mut subst, &[1, count]);
+    for (first, _) in sets {
+        write_u16s(&mut subst, &[*first]);
+    }
+
+    subst
+}
+
+/// Append big-endian 16-bit values to a table
+fn write_u16s(table: &mut Vec<u8>, values: &[u16]) {
+    for value in values {
+        table.extend(value.to_be_bytes());
+    }
+}
+
+#[cfg(all(test, feature = "outlined"))]
+mod tests {
+    use super::*;
+    use crate::font::{test_fonts::whole_font, Font};
+
+    #[test]
+    fn test_subset() {
+        use crate::IconName as Icon;
+
+        let font = whole_font(crate::Style::Outlined);
+        let subset = font.subset(&[Icon::Add, Icon::Home]).unwrap();
+        assert!(subset.missing.is_empty());
+
+        let font = Font::new(&subset.data).unwrap();
+        let add = font.index_of(Icon::Add as u32).unwrap();
+        let home = font.index_of(Icon::Home as u32).unwrap();
+        assert_ne!(add, 0);
+        assert_ne!(home, 0);
+
+        // The ligatures still spell out the icon names
+        let spell = |font: &Font, name: &str| -> Vec<u16> {
+            name.chars()
+                .map(|c| font.index_of(c as u32).unwrap())
+                .collect()
+        };
+        let ligatures = font.ligature_glyphs().unwrap();
+        assert!(ligatures.contains(&(add, spell(&font, "add"))));
+        assert!(ligatures.contains(&(home, spell(&font, "home"))));
+    }
+
+    #[test]
+    fn test_subset_missing() {
+        use crate::IconName as Icon;
+
+        let font = whole_font(crate::Style::Outlined);
+        let subset = font.subset(&[Icon::Add as u32, 0x10FFFF]).unwrap();
+        assert_eq!(subset.missing, vec![0x10FFFF]);
+
+        let font = Font::new(&subset.data).unwrap();
+        assert_ne!(font.index_of(Icon::Add as u32).unwrap(), 0);
+        assert_eq!(font.index_of(Icon::Home as u32).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_ligature_features() {
+        use crate::IconName as Icon;
+
+        let font = whole_font(crate::Style::Outlined);
+        let add = font.index_of(Icon::Add as u32).unwrap();
+        let ligatures = ligature_glyphs(&font.table_provider()).unwrap();
+        let ligatures = ligatures
+            .into_iter()
+            .filter(|(id, _)| *id == add)
+            .collect::<Vec<_>>();
+
+        // Rebuild the font with the ligature under a different feature or script
+        type Retags = &'static [(&'static [u8; 4], &'static [u8; 4])];
+        let with_tags = |tags: Retags| {
+            let mut gsub = ligature_gsub(&ligatures);
+            for (from, to) in tags {
+                let at = gsub.windows(4).position(|tag| tag == *from).unwrap();
+                gsub[at..at + 4].copy_from_slice(*to);
+            }
+            with_tables(font.font_data(), vec![(tag::GSUB, gsub)]).unwrap()
+        };
+
+        // Only the ligatures of the `rlig` and `liga` features, for Latin or the default script, spell out names
+        let cases: [(Retags, bool); 4] = [
+            (&[], true),
+            (&[(b"rlig", b"liga")], true),
+            (&[(b"rlig", b"ss01")], false),
+            (&[(b"DFLT", b"arab"), (b"latn", b"grek")], false),
+        ];
+        for (tags, spelled) in cases {
+            let data = with_tags(tags);
+            let font = Font::new(&data).unwrap();
+            let expected = spelled.then_some(add);
+            assert_eq!(font.lookup_ligature("add"), expected, "{tags:?}");
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! Icon lists for build-time subsetting
+//!
+//! Reads the icons named in `MATERIAL_DESIGN_ICONS_SUBSET`, and finds the glyph of each one in a font
+//!
+//! This file is included by the build script, which subsets the fonts embedded by the `subset` feature,
+//! and by the crate's tests - so it must only depend on `allsorts` and the `subset` module
+//!
+use super::subset::ligature_glyphs;
+use allsorts::{
+    binary::read::{ReadBinary, ReadScope},
+    error::ParseError,
+    font::Font,
+    tables::{cmap::CmapSubtable, FontTableProvider},
+};
+use std::collections::BTreeSet;
+
+/// Split a list of icon names, separated by commas or whitespace  
+/// Returns an error if the list does not name any icons
+pub fn parse_icon_list(list: &str) -> Result<Vec<String>, SubsetListError> {
+    let icons = list
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+
+    match icons.is_empty() {
+        true => Err(SubsetListError::Empty),
+        false => Ok(icons),
+    }
+}
+
+/// Find the glyph of each named icon in a font  
+/// Names are matched as in `IconName::from_name`, except that the old names in `IconName::RENAMED` are not accepted
+///
+/// Returns an error for the first name that is not an icon - including names of support glyphs, such as `"a"`
+pub fn icon_glyphs<T: FontTableProvider>(
+    font: &Font<T>,
+    icons: &[String],
+) -> Result<BTreeSet<u16>, SubsetListError> {
+    const PRIVATE_USE: &[std::ops::RangeInclusive<u32>] =
+        &[0xE000..=0xF8FF, 0xF0000..=0xFFFFD, 0x100000..=0x10FFFD];
+
+    let cmap = CmapSubtable::read(&mut ReadScope::new(font.cmap_subtable_data()).ctxt())?;
+
+    // As in the generated enum, icons are the glyphs mapped from the private use area
+    let mut icon_ids = BTreeSet::new();
+    cmap.mappings_fn(|codepoint, glyph| {
+        if PRIVATE_USE.iter().any(|r| r.contains(&codepoint)) {
+            icon_ids.insert(glyph);
+        }
+    })?;
+    let ids = icon_ids.iter().copied().collect::<Vec<_>>();
+    let names = font.glyph_names(&ids);
+
+    let mut ligatures = None;
+    let mut glyph_ids = BTreeSet::new();
+    for icon in icons {
+        // Names starting with a digit can leave out the leading underscore of the glyph name
+        let glyph_name = if icon.starts_with(|c: char| c.is_ascii_digit()) {
+            format!("_{icon}")
+        } else {
+            icon.clone()
+        };
+        let glyph_id = match names
+            .iter()
+            .position(|name| name == icon || *name == glyph_name)
+        {
+            Some(index) => Some(ids[index]),
+
+            // Aliases resolve through the ligature that spells them out
+            None => {
+                let ligatures = match &ligatures {
+                    Some(ligatures) => ligatures,
+                    None => ligatures.insert(ligature_glyphs(&font.font_table_provider)?),
+                };
+                let components = icon
+                    .chars()
+                    .map(|c| cmap.map_glyph(c as u32).ok().flatten())
+                    .collect::<Option<Vec<_>>>();
+                components.and_then(|components| {
+                    ligatures
+                        .iter()
+                        .find(|(_, glyphs)| *glyphs == components)
+                        .map(|(glyph, _)| *glyph)
+                })
+            }
+        };
+
+        match glyph_id {
+            Some(glyph_id) if icon_ids.contains(&glyph_id) => glyph_ids.insert(glyph_id),
+            _ => return Err(SubsetListError::NotAnIcon(icon.clone())),
+        };
+    }
+
+    Ok(glyph_ids)
+}
+
+/// Error type for reading a list of icons to subset a font to
+#[derive(Debug, PartialEq)]
+pub enum SubsetListError {
+    /// The list does not name any icons
+    Empty,
+
+    /// A name in the list is not an icon in the font
+    NotAnIcon(String),
+
+    Parse(ParseError),
+}
+impl From<ParseError> for SubsetListError {
+    fn from(err: ParseError) -> Self {
+        SubsetListError::Parse(err)
+    }
+}
+impl std::fmt::Display for SubsetListError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubsetListError::Empty => write!(f, "The list does not name any icons"),
+            SubsetListError::NotAnIcon(name) => write!(f, "`{}` is not an icon", name),
+            SubsetListError::Parse(err) => write!(f, "Parse error: {}", err),
+        }
+    }
+}
+impl std::error::Error for SubsetListError {}
+
+#[cfg(all(test, feature = "outlined"))]
+mod tests {
+    use super::*;
+    use crate::font::test_fonts::whole_font;
+    use allsorts::font_data::FontData;
+
+    #[test]
+    fn test_parse_icon_list() {
+        assert_eq!(
+            parse_icon_list("add, home\tdelete,,10k"),
+            Ok(vec![
+                "add".to_string(),
+                "home".to_string(),
+                "delete".to_string(),
+                "10k".to_string()
+            ])
+        );
+
+        // A list of only separators would keep nothing but `.notdef`
+        assert_eq!(parse_icon_list(""), Err(SubsetListError::Empty));
+        assert_eq!(parse_icon_list(" , ,\n"), Err(SubsetListError::Empty));
+    }
+
+    #[test]
+    fn test_icon_glyphs() {
+        let data = whole_font(crate::Style::Outlined);
+        let font_data = ReadScope::new(data.font_data())
+            .read::<FontData<'_>>()
+            .unwrap();
+        let font = Font::new(font_data.table_provider(0).unwrap()).unwrap();
+        let names = |names: &[&str]| names.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        // Glyph names, names without their leading underscore, and aliases all resolve
+        let glyphs = icon_glyphs(&font, &names(&["add", "10k", "_10k", "home_filled"])).unwrap();
+        let index = |icon: crate::IconName| data.index_of(icon as u32).unwrap();
+        let expected = [
+            index(crate::IconName::Add),
+            index(crate::IconName::_10k),
+            index(crate::IconName::Home),
+        ];
+        assert_eq!(glyphs, BTreeSet::from(expected));
+
+        // Support glyphs and unknown names are rejected
+        for name in ["a", "space", ".notdef", "not_an_icon"] {
+            assert_eq!(
+                icon_glyphs(&font, &names(&["add", name])),
+                Err(SubsetListError::NotAnIcon(name.to_string()))
+            );
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! SVG export
+//!
+//! Writes the outline of an icon as a standalone SVG image, flipped into SVG's y-down coordinates
+//!
+use super::{Font, FontError, IconPath, PathCommand, Point};
+use crate::IconVariation;
+use std::fmt::Write;
+
+/// Options for [`Font::to_svg`]
+///
+/// ```rust
+/// use material_design_icons::{font::SvgOptions, IconVariation};
+/// let options = SvgOptions::default()
+///     .with_size(48.0)
+///     .with_color("#1a73e8")
+///     .with_title("Add")
+///     .with_variation(IconVariation::FILLED);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgOptions {
+    /// Width and height of the icon, in pixels
+    pub size: f32,
+
+    /// Space around the icon on each side, in pixels - the image is `size + 2 * padding` across
+    pub padding: f32,
+
+    /// The fill color, as any CSS color
+    /// The default, `currentColor`, draws the icon in the color of the surrounding text
+    pub color: String,
+
+    /// A title for the image, read out by screen readers and shown as a tooltip
+    pub title: Option<String>,
+
+    /// The variation to draw the icon at
+    pub variation: IconVariation,
+}
+impl SvgOptions {
+    /// Return a copy with a different size
+    pub fn with_size(self, size: f32) -> Self {
+        Self { size, ..self }
+    }
+
+    /// Return a copy with different padding
+    pub fn with_padding(self, padding: f32) -> Self {
+        Self { padding, ..self }
+    }
+
+    /// Return a copy with a different fill color
+    pub fn with_color(self, color: impl Into<String>) -> Self {
+        Self {
+            color: color.into(),
+            ..self
+        }
+    }
+
+    /// Return a copy with a title
+    pub fn with_title(self, title: impl Into<String>) -> Self {
+        Self {
+            title: Some(title.into()),
+            ..self
+        }
+    }
+
+    /// Return a copy with a different variation
+    pub fn with_variation(self, variation: IconVariation) -> Self {
+        Self { variation, ..self }
+    }
+}
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            size: 24.0,
+            padding: 0.0,
+            color: "currentColor".to_string(),
+            title: None,
+            variation: IconVariation::DEFAULT,
+        }
+    }
+}
+
+impl Font<'_> {
+    /// Draw an icon as an SVG image
+    /// Accepts an icon, a char or a raw codepoint
+    ///
+    /// The viewBox is the font's em square, in font units, so the icon keeps the same position
+    /// and margins it has in the font
+    pub fn to_svg(&self, icon: impl Into<u32>, options: &SvgOptions) -> Result<String, FontError> {
+        let path = self.icon_outline(icon.into(), &options.variation)?;
+
+        // Padding is given in pixels, but the viewBox is in font units
+        let em = f32::from(path.units_per_em);
+        let padding = match options.size > 0.0 {
+            true => options.padding * em / options.size,
+            false => 0.0,
+        };
+        let size = options.size + 2.0 * options.padding;
+        let view_size = em + 2.0 * padding;
+
+        let mut svg = String::new();
+        let _ = write!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{size}" viewBox="{min} {min} {view_size} {view_size}""#,
+            size = number(size),
+            min = number(-padding),
+            view_size = number(view_size),
+        );
+        match &options.title {
+            Some(title) => {
+                let _ = write!(svg, r#" role="img"><title>{}</title>"#, escape(title));
+            }
+            None => svg.push_str(r#" aria-hidden="true">"#),
+        }
+        let _ = write!(
+            svg,
+            r#"<path fill="{}" d="{}"/></svg>"#,
+            escape(&options.color),
+            path.to_svg_path()
+        );
+
+        Ok(svg)
+    }
+}
+
+impl IconPath {
+    /// Return the path as SVG path data, for the `d` attribute of a `<path>`
+    /// The y-axis is flipped, so the em square runs from `0` at the top to `units_per_em` at the bottom
+    pub fn to_svg_path(&self) -> String {
+        let em = f32::from(self.units_per_em);
+        let point = |p: &Point| format!("{} {}", number(p.x), number(em - p.y));
+
+        let mut data = Vec::with_capacity(self.commands.len());
+        for command in &self.commands {
+            data.push(match command {
+                PathCommand::MoveTo(to) => format!("M{}", point(to)),
+                PathCommand::LineTo(to) => format!("L{}", point(to)),
+                PathCommand::QuadTo(control, to) => format!("Q{} {}", point(control), point(to)),
+                PathCommand::Close => "Z".to_string(),
+            });
+        }
+        data.concat()
+    }
+}
+
+/// Format a number for SVG output, with at most 2 decimal places and no trailing zeros
+fn number(value: f32) -> String {
+    let value = (value * 100.0).round() / 100.0;
+
+    // Avoid writing out negative zero
+    match value == 0.0 {
+        true => "0".to_string(),
+        false => value.to_string(),
+    }
+}
+
+/// Escape text for use in XML content or attribute values
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(all(test, feature = "sharp"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(not(icons_subset))]
+    fn test_to_svg() {
+        use crate::IconName as Icon;
+
+        let font = Font::new_sharp().unwrap();
+        let svg = font.to_svg(Icon::Add, &SvgOptions::default()).unwrap();
+        assert!(svg.starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="24" height="24" viewBox="0 0 960 960" aria-hidden="true"><path fill="currentColor" d="M"#
+        ));
+        assert!(svg.ends_with(r#"Z"/></svg>"#));
+
+        let options = SvgOptions::default()
+            .with_size(48.0)
+            .with_padding(4.0)
+            .with_color("#ff0000")
+            .with_title("Add <new> & \"more\"");
+        let svg = font.to_svg(Icon::Add, &options).unwrap();
+        assert!(svg.contains(r#"width="56" height="56" viewBox="-80 -80 1120 1120""#));
+        assert!(
+            svg.contains(r#" role="img"><title>Add &lt;new&gt; &amp; &quot;more&quot;</title>"#)
+        );
+        assert!(svg.contains(r##"fill="#ff0000""##));
+
+        let filled = SvgOptions::default().with_variation(IconVariation::FILLED);
+        assert_ne!(
+            font.to_svg(Icon::Delete, &filled).unwrap(),
+            font.to_svg(Icon::Delete, &SvgOptions::default()).unwrap()
+        );
+
+        assert!(matches!(
+            font.to_svg(0x10FFFF_u32, &SvgOptions::default()),
+            Err(FontError::MissingGlyph(0x10FFFF))
+        ));
+    }
+
+    #[test]
+    fn test_svg_path() {
+        let p = |x, y| Point::new(x, y);
+        let path = IconPath {
+            commands: vec![
+                PathCommand::MoveTo(p(0.0, 0.0)),
+                PathCommand::LineTo(p(100.0, 0.0)),
+                PathCommand::QuadTo(p(100.0, 100.0), p(50.5, 100.125)),
+                PathCommand::Close,
+            ],
+            units_per_em: 100,
+        };
+        assert_eq!(path.to_svg_path(), "M0 100L100 100Q100 0 50.5 -0.13Z");
+    }
+}
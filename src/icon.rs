@@ -1,61 +1,926 @@
-/// Macro implementing the `Icon` features for all the auto-extracted font data
-macro_rules! impl_icon {
-    ($name:ident, $font_name:literal) => {
-        impl From<$name> for char {
-            fn from(value: $name) -> Self {
-                // Safety: All codepoints are google-provided and should be valid
-                std::char::from_u32(value as u32).unwrap_or(char::REPLACEMENT_CHARACTER)
+//! Style-independent icon names, and the styles they can be drawn in
+use crate::{glyphs::IconName, IconVariation};
+
+impl From<IconName> for u32 {
+    fn from(value: IconName) -> Self {
+        value as u32
+    }
+}
+
+impl From<IconName> for char {
+    fn from(value: IconName) -> Self {
+        // Safety: All codepoints are google-provided and should be valid
+        std::char::from_u32(value as u32).unwrap_or(char::REPLACEMENT_CHARACTER)
+    }
+}
+
+impl std::str::FromStr for IconName {
+    type Err = IconError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_name(s).ok_or_else(|| IconError::UnknownName(s.to_string()))
+    }
+}
+
+impl TryFrom<u32> for IconName {
+    type Error = IconError;
+
+    fn try_from(value: u32) -> Result<Self, IconError> {
+        Self::from_codepoint(value).ok_or(IconError::UnknownCodepoint(value))
+    }
+}
+
+impl TryFrom<char> for IconName {
+    type Error = IconError;
+
+    fn try_from(value: char) -> Result<Self, IconError> {
+        Self::try_from(value as u32)
+    }
+}
+
+impl std::fmt::Display for IconName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", char::from(*self))
+    }
+}
+
+impl IconName {
+    /// The number of icons in the font
+    pub const COUNT: usize = Self::ALL.len();
+
+    /// Iterate over every icon in the font, in declaration order
+    pub fn iter() -> std::iter::Copied<std::slice::Iter<'static, Self>> {
+        Self::ALL.iter().copied()
+    }
+
+    /// Look up an icon by its Material glyph name, such as `"add_circle"`  
+    /// Names starting with a digit are also accepted as their ligature, without the leading underscore - so `"10k"` finds `_10k`  
+    /// Also accepts the alias names listed in [`IconName::ALIASES`], and the old names in [`IconName::RENAMED`]
+    pub const fn from_name(name: &str) -> Option<Self> {
+        Self::from_name_renamed(name, Self::RENAMED)
+    }
+
+    /// Look up an icon by name as [`IconName::from_name`] does, but with the given table of old names  
+    /// Lets the renamed lookups be tested while the font has no renamed icons
+    pub(crate) const fn from_name_renamed(
+        name: &str,
+        renamed: &[(&'static str, Self)],
+    ) -> Option<Self> {
+        if let Some(icon) = find(Self::NAMES, b"", name) {
+            return Some(icon);
+        }
+
+        if let [first, ..] = name.as_bytes() {
+            if first.is_ascii_digit() {
+                if let Some(icon) = find(Self::NAMES, b"_", name) {
+                    return Some(icon);
+                }
             }
         }
 
-        impl std::fmt::Display for $name {
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                write!(f, "{}", char::from(*self))
+        if let Some(icon) = find(Self::ALIASES, b"", name) {
+            return Some(icon);
+        }
+        find(renamed, b"", name)
+    }
+
+    /// Return the icon names closest to a misspelled one, best match first  
+    /// Useful for suggesting a fix when [`IconName::from_name`] fails
+    pub fn similar_names(name: &str) -> Vec<&'static str> {
+        Self::nearest_names(name).into_iter().flatten().collect()
+    }
+
+    /// Find up to 3 icon names within a few edits of a misspelled one, best match first  
+    /// Ties are broken by name. This is const so that the `icon!` macro can suggest names at compile time
+    pub(crate) const fn nearest_names(name: &str) -> [Option<&'static str>; 3] {
+        const MAX_SUGGESTIONS: usize = 3;
+        let max_distance = if name.len() / 3 > 2 {
+            name.len() / 3
+        } else {
+            2
+        };
+
+        let mut nearest: [(usize, Option<&'static str>); MAX_SUGGESTIONS] =
+            [(usize::MAX, None); MAX_SUGGESTIONS];
+        let mut i = 0;
+        while i < Self::NAMES.len() {
+            let entry = Self::NAMES[i].0;
+            let distance = edit_distance(name.as_bytes(), entry.as_bytes());
+            i += 1;
+            if distance > max_distance {
+                continue;
+            }
+
+            // Insert in order of distance - names are already sorted, so ties keep name order
+            let mut slot = MAX_SUGGESTIONS;
+            while slot > 0 && distance < nearest[slot - 1].0 {
+                slot -= 1;
+            }
+            if slot == MAX_SUGGESTIONS {
+                continue;
             }
+
+            let mut j = MAX_SUGGESTIONS - 1;
+            while j > slot {
+                nearest[j] = nearest[j - 1];
+                j -= 1;
+            }
+            nearest[slot] = (distance, Some(entry));
         }
 
-        #[cfg(feature = "iced")]
-        #[cfg_attr(docsrs, doc(cfg(feature = "iced")))]
-        impl<'a, Message> From<$name> for iced::Element<'a, Message> {
-            fn from(value: $name) -> Self {
-                value
-                    .into_text(iced::settings::Settings::default().default_text_size)
-                    .into()
+        [nearest[0].1, nearest[1].1, nearest[2].1]
+    }
+
+    /// Return the Material glyph name of the icon, such as `"add_circle"`  
+    /// Glyph names can't start with a digit, so icons like `10k` are named `"_10k"` - use
+    /// [`IconName::ligature`] for the text that the font turns into the icon
+    pub const fn name(self) -> &'static str {
+        match Self::codepoint_index(self as u32) {
+            Some(index) => Self::CODEPOINTS[index].1,
+
+            // Every variant is present in the table
+            None => unreachable!(),
+        }
+    }
+
+    /// Return the ligature that the font turns into the icon, such as `"add_circle"` or `"10k"`  
+    /// This is the glyph name, without the leading underscore given to names starting with a digit
+    pub const fn ligature(self) -> &'static str {
+        let name = self.name();
+        match name.as_bytes() {
+            [b'_', rest @ ..] if !rest.is_empty() && rest[0].is_ascii_digit() => {
+                match std::str::from_utf8(rest) {
+                    Ok(ligature) => ligature,
+
+                    // Only an ASCII byte was removed
+                    Err(_) => unreachable!(),
+                }
+            }
+            _ => name,
+        }
+    }
+
+    /// Look up an icon by its codepoint
+    pub const fn from_codepoint(codepoint: u32) -> Option<Self> {
+        match Self::codepoint_index(codepoint) {
+            Some(index) => Some(Self::CODEPOINTS[index].0),
+            None => None,
+        }
+    }
+
+    /// Pair the icon with a style, so that it can be drawn
+    pub const fn styled(self, style: Style) -> StyledIcon {
+        StyledIcon::new(self, style)
+    }
+
+    /// Binary search the codepoint table
+    const fn codepoint_index(codepoint: u32) -> Option<usize> {
+        let (mut low, mut high) = (0, Self::CODEPOINTS.len());
+        while low < high {
+            let mid = (low + high) / 2;
+            let entry = Self::CODEPOINTS[mid].0 as u32;
+            if entry == codepoint {
+                return Some(mid);
+            } else if entry < codepoint {
+                low = mid + 1;
+            } else {
+                high = mid;
             }
         }
 
-        impl $name {
-            /// Convert the icon to an iced Text widget
-            #[cfg(feature = "iced")]
-            #[cfg_attr(docsrs, doc(cfg(feature = "iced")))]
-            pub fn into_text<'a, Theme>(
-                self,
-                font_size: impl Into<iced::Pixels>,
-            ) -> iced::widget::Text<'a, Theme>
-            where
-                Theme: iced::widget::text::Catalog,
-            {
-                const ICON_FONT: iced::font::Family = iced::font::Family::Name($font_name);
-                iced::widget::Text::new(char::from(self))
-                    .font(iced::font::Font {
-                        family: ICON_FONT,
-                        ..Default::default()
-                    })
-                    .size(font_size)
+        None
+    }
+}
+
+/// Binary search a name table for `prefix` followed by `name`
+const fn find(table: &[(&str, IconName)], prefix: &[u8], name: &str) -> Option<IconName> {
+    let (mut low, mut high) = (0, table.len());
+    while low < high {
+        let mid = (low + high) / 2;
+        let (entry, icon) = table[mid];
+        match compare(entry.as_bytes(), prefix, name.as_bytes()) {
+            std::cmp::Ordering::Equal => return Some(icon),
+            std::cmp::Ordering::Less => low = mid + 1,
+            std::cmp::Ordering::Greater => high = mid,
+        }
+    }
+
+    None
+}
+
+/// Compare a table entry with `prefix` followed by `name`, byte by byte
+const fn compare(entry: &[u8], prefix: &[u8], name: &[u8]) -> std::cmp::Ordering {
+    let len = prefix.len() + name.len();
+    let mut i = 0;
+    while i < entry.len() && i < len {
+        let byte = if i < prefix.len() {
+            prefix[i]
+        } else {
+            name[i - prefix.len()]
+        };
+
+        if entry[i] != byte {
+            return if entry[i] < byte {
+                std::cmp::Ordering::Less
+            } else {
+                std::cmp::Ordering::Greater
+            };
+        }
+        i += 1;
+    }
+
+    if entry.len() < len {
+        std::cmp::Ordering::Less
+    } else if entry.len() > len {
+        std::cmp::Ordering::Greater
+    } else {
+        std::cmp::Ordering::Equal
+    }
+}
+
+/// Levenshtein distance between two names  
+/// Names longer than any glyph name are never considered close
+const fn edit_distance(a: &[u8], b: &[u8]) -> usize {
+    const MAX_LEN: usize = 64;
+    if b.len() >= MAX_LEN {
+        return usize::MAX;
+    }
+
+    let mut row = [0; MAX_LEN];
+    let mut j = 0;
+    while j <= b.len() {
+        row[j] = j;
+        j += 1;
+    }
+
+    let mut i = 0;
+    while i < a.len() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        let mut j = 0;
+        while j < b.len() {
+            let substitution = diagonal + (a[i] != b[j]) as usize;
+            let insertion = row[j] + 1;
+            let deletion = row[j + 1] + 1;
+            diagonal = row[j + 1];
+
+            row[j + 1] = if substitution < insertion {
+                substitution
+            } else {
+                insertion
+            };
+            if deletion < row[j + 1] {
+                row[j + 1] = deletion;
             }
+            j += 1;
+        }
+        i += 1;
+    }
+
+    row[b.len()]
+}
+
+/// The visual style of a Material Symbols font  
+/// With the `serde` feature, styles are serialized by their lowercase name, such as `"sharp"`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Style {
+    /// Icons from the Material Symbols Outlined font
+    #[cfg(feature = "outlined")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "outlined")))]
+    Outlined,
+
+    /// Icons from the Material Symbols Rounded font
+    #[cfg(feature = "rounded")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rounded")))]
+    Rounded,
+
+    /// Icons from the Material Symbols Sharp font
+    #[cfg(feature = "sharp")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sharp")))]
+    Sharp,
+}
+impl Style {
+    /// Every style enabled by the crate features
+    pub const ALL: &'static [Self] = &[
+        #[cfg(feature = "outlined")]
+        Self::Outlined,
+        #[cfg(feature = "rounded")]
+        Self::Rounded,
+        #[cfg(feature = "sharp")]
+        Self::Sharp,
+    ];
+
+    /// Return the raw data of the font for this style
+    pub fn icon_font(self) -> &'static [u8] {
+        match self {
+            #[cfg(feature = "outlined")]
+            Self::Outlined => crate::outlined::icon_font(),
+            #[cfg(feature = "rounded")]
+            Self::Rounded => crate::rounded::icon_font(),
+            #[cfg(feature = "sharp")]
+            Self::Sharp => crate::sharp::icon_font(),
+        }
+    }
+
+    /// Return the family name of the font for this style
+    pub const fn family_name(self) -> &'static str {
+        match self {
+            #[cfg(feature = "outlined")]
+            Self::Outlined => "Material Symbols Outlined",
+            #[cfg(feature = "rounded")]
+            Self::Rounded => "Material Symbols Rounded",
+            #[cfg(feature = "sharp")]
+            Self::Sharp => "Material Symbols Sharp",
+        }
+    }
+
+    /// Return the family name and raw data of a static font for this style at a variation  
+    /// The default variation is the variable font itself - other variations are only available
+    /// if they were pre-baked by the `filled`, `bold` or `filled-bold` features
+    pub fn instance_font(self, variation: &IconVariation) -> Option<(&'static str, &'static [u8])> {
+        if *variation == IconVariation::DEFAULT {
+            return Some((self.family_name(), self.icon_font()));
+        }
+
+        self.instances()
+            .iter()
+            .find(|(baked, _, _)| baked == variation)
+            .map(|(_, family_name, icon_font)| (*family_name, icon_font()))
+    }
+
+    /// Return the instances of this style pre-baked by the build script
+    fn instances(self) -> &'static [crate::embed::Instance] {
+        match self {
+            #[cfg(feature = "outlined")]
+            Self::Outlined => crate::outlined::INSTANCES,
+            #[cfg(feature = "rounded")]
+            Self::Rounded => crate::rounded::INSTANCES,
+            #[cfg(feature = "sharp")]
+            Self::Sharp => crate::sharp::INSTANCES,
+        }
+    }
+
+    /// Pair an icon with this style, so that it can be drawn
+    pub const fn icon(self, name: IconName) -> StyledIcon {
+        StyledIcon::new(name, self)
+    }
+}
+
+/// An icon in a specific style  
+/// Unlike [`IconName`], this knows which font it needs to be drawn with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StyledIcon {
+    pub name: IconName,
+    pub style: Style,
+}
+impl StyledIcon {
+    /// Create a new styled icon
+    pub const fn new(name: IconName, style: Style) -> Self {
+        Self { name, style }
+    }
+
+    /// Return the same icon in a different style
+    pub const fn with_style(self, style: Style) -> Self {
+        Self::new(self.name, style)
+    }
+
+    /// Convert the icon to an iced Text widget
+    #[cfg(feature = "iced")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "iced")))]
+    pub fn into_text<'a, Theme>(
+        self,
+        font_size: impl Into<iced::Pixels>,
+    ) -> iced::widget::Text<'a, Theme>
+    where
+        Theme: iced::widget::text::Catalog,
+    {
+        MaterialIcon::into_text(self, font_size)
+    }
+
+    /// Convert the icon to an iced Text widget, drawn at a variation  
+    /// Falls back to the default variation if the requested one was not pre-baked
+    #[cfg(feature = "iced")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "iced")))]
+    pub fn into_text_with<'a, Theme>(
+        self,
+        font_size: impl Into<iced::Pixels>,
+        variation: &IconVariation,
+    ) -> iced::widget::Text<'a, Theme>
+    where
+        Theme: iced::widget::text::Catalog,
+    {
+        MaterialIcon::into_text_with(self, font_size, variation)
+    }
+}
+
+impl MaterialIcon for StyledIcon {
+    fn codepoint(&self) -> u32 {
+        self.name as u32
+    }
+
+    fn name(&self) -> &'static str {
+        self.name.name()
+    }
+
+    fn icon_font(&self) -> &'static [u8] {
+        self.style.icon_font()
+    }
+
+    fn family_name(&self) -> &'static str {
+        self.style.family_name()
+    }
+
+    fn style(&self) -> Style {
+        self.style
+    }
+}
+
+impl From<StyledIcon> for char {
+    fn from(value: StyledIcon) -> Self {
+        char::from(value.name)
+    }
+}
+
+impl std::fmt::Display for StyledIcon {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+#[cfg(feature = "iced")]
+#[cfg_attr(docsrs, doc(cfg(feature = "iced")))]
+impl<'a, Message> From<StyledIcon> for iced::Element<'a, Message> {
+    fn from(value: StyledIcon) -> Self {
+        value
+            .into_text(iced::settings::Settings::default().default_text_size)
+            .into()
+    }
+}
+
+/// Marks the style of a [`TypedIcon`]  
+/// Implemented by the marker type of each style module, such as `sharp::Sharp`
+pub trait StyleMarker: Copy + 'static {
+    /// The style of icons marked with this type
+    const STYLE: Style;
+}
+
+/// An icon whose style is part of its type  
+/// Each style module exports one as `Icon`, such as `sharp::Icon`, with a constant for every icon in [`IconName`]
+///
+/// Unlike [`StyledIcon`], the style can't be changed at runtime - convert between styles with `From`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TypedIcon<S>(IconName, std::marker::PhantomData<S>);
+
+impl<S: StyleMarker> TypedIcon<S> {
+    /// The number of icons in the font
+    pub const COUNT: usize = IconName::COUNT;
+
+    /// Create a new icon in this style
+    pub const fn new(name: IconName) -> Self {
+        Self(name, std::marker::PhantomData)
+    }
+
+    /// Iterate over every icon in the font, in declaration order
+    pub fn iter() -> impl Iterator<Item = Self> {
+        IconName::iter().map(Self::new)
+    }
+
+    /// Look up an icon by its Material glyph name, as [`IconName::from_name`] does
+    pub const fn from_name(name: &str) -> Option<Self> {
+        match IconName::from_name(name) {
+            Some(name) => Some(Self::new(name)),
+            None => None,
+        }
+    }
+
+    /// Look up an icon by its codepoint
+    pub const fn from_codepoint(codepoint: u32) -> Option<Self> {
+        match IconName::from_codepoint(codepoint) {
+            Some(name) => Some(Self::new(name)),
+            None => None,
+        }
+    }
+
+    /// Return the icon, without its style
+    pub const fn icon_name(self) -> IconName {
+        self.0
+    }
+
+    /// Return the Material glyph name of the icon, such as `"add_circle"`
+    pub const fn name(self) -> &'static str {
+        self.0.name()
+    }
+
+    /// Return the ligature that the font turns into the icon, such as `"add_circle"` or `"10k"`
+    pub const fn ligature(self) -> &'static str {
+        self.0.ligature()
+    }
+
+    /// Return the icon as a [`StyledIcon`], whose style can be changed at runtime
+    pub const fn styled(self) -> StyledIcon {
+        StyledIcon::new(self.0, S::STYLE)
+    }
+
+    /// Return the same icon in another style
+    pub const fn with_style<T: StyleMarker>(self) -> TypedIcon<T> {
+        TypedIcon::new(self.0)
+    }
+
+    /// Convert the icon to an iced Text widget
+    #[cfg(feature = "iced")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "iced")))]
+    pub fn into_text<'a, Theme>(
+        self,
+        font_size: impl Into<iced::Pixels>,
+    ) -> iced::widget::Text<'a, Theme>
+    where
+        Theme: iced::widget::text::Catalog,
+    {
+        MaterialIcon::into_text(self, font_size)
+    }
+
+    /// Convert the icon to an iced Text widget, drawn at a variation  
+    /// Falls back to the default variation if the requested one was not pre-baked
+    #[cfg(feature = "iced")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "iced")))]
+    pub fn into_text_with<'a, Theme>(
+        self,
+        font_size: impl Into<iced::Pixels>,
+        variation: &IconVariation,
+    ) -> iced::widget::Text<'a, Theme>
+    where
+        Theme: iced::widget::text::Catalog,
+    {
+        MaterialIcon::into_text_with(self, font_size, variation)
+    }
+}
+
+/// Declares a constant on [`TypedIcon`] for every icon, alias and renamed icon in [`IconName`]
+macro_rules! typed_icon_consts {
+    (
+        icons: [$($icon:ident),* $(,)?],
+        aliases: [$($alias:ident),* $(,)?],
+        renamed: [$($renamed:ident => $note:literal),* $(,)?],
+    ) => {
+        #[allow(non_upper_case_globals)]
+        impl<S: StyleMarker> TypedIcon<S> {
+            /// Every icon in the font, in declaration order
+            pub const ALL: &'static [Self] = &[$(Self::new(IconName::$icon)),*];
+
+            $(
+                #[doc = concat!("[`IconName::", stringify!($icon), "`] in this style")]
+                pub const $icon: Self = Self::new(IconName::$icon);
+            )*
+
+            $(
+                #[doc = concat!("[`IconName::", stringify!($alias), "`] in this style")]
+                pub const $alias: Self = Self::new(IconName::$alias);
+            )*
+
+            $(
+                #[doc = concat!("[`IconName::", stringify!($renamed), "`] in this style")]
+                #[deprecated(note = $note)]
+                #[allow(deprecated)]
+                pub const $renamed: Self = Self::new(IconName::$renamed);
+            )*
         }
     };
 }
+icon_identifiers!(typed_icon_consts);
+
+impl<S: StyleMarker> MaterialIcon for TypedIcon<S> {
+    fn codepoint(&self) -> u32 {
+        self.0 as u32
+    }
+
+    fn name(&self) -> &'static str {
+        self.0.name()
+    }
+
+    fn icon_font(&self) -> &'static [u8] {
+        S::STYLE.icon_font()
+    }
+
+    fn family_name(&self) -> &'static str {
+        S::STYLE.family_name()
+    }
+
+    fn style(&self) -> Style {
+        S::STYLE
+    }
+}
+
+impl<S: StyleMarker> From<IconName> for TypedIcon<S> {
+    fn from(value: IconName) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<S: StyleMarker> From<TypedIcon<S>> for IconName {
+    fn from(value: TypedIcon<S>) -> Self {
+        value.0
+    }
+}
+
+impl<S: StyleMarker> From<TypedIcon<S>> for StyledIcon {
+    fn from(value: TypedIcon<S>) -> Self {
+        value.styled()
+    }
+}
+
+impl<S: StyleMarker> From<TypedIcon<S>> for u32 {
+    fn from(value: TypedIcon<S>) -> Self {
+        value.0 as u32
+    }
+}
+
+impl<S: StyleMarker> From<TypedIcon<S>> for char {
+    fn from(value: TypedIcon<S>) -> Self {
+        char::from(value.0)
+    }
+}
+
+impl<S: StyleMarker> std::str::FromStr for TypedIcon<S> {
+    type Err = IconError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self::new)
+    }
+}
+
+impl<S: StyleMarker> TryFrom<u32> for TypedIcon<S> {
+    type Error = IconError;
+
+    fn try_from(value: u32) -> Result<Self, IconError> {
+        IconName::try_from(value).map(Self::new)
+    }
+}
+
+impl<S: StyleMarker> TryFrom<char> for TypedIcon<S> {
+    type Error = IconError;
+
+    fn try_from(value: char) -> Result<Self, IconError> {
+        IconName::try_from(value).map(Self::new)
+    }
+}
+
+impl<S: StyleMarker> std::fmt::Display for TypedIcon<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(feature = "iced")]
+#[cfg_attr(docsrs, doc(cfg(feature = "iced")))]
+impl<'a, Message, S: StyleMarker> From<TypedIcon<S>> for iced::Element<'a, Message> {
+    fn from(value: TypedIcon<S>) -> Self {
+        value
+            .into_text(iced::settings::Settings::default().default_text_size)
+            .into()
+    }
+}
+
+/// Common interface for the icons of every style  
+/// Use this to accept any Material icon generically
+pub trait MaterialIcon {
+    /// Return the codepoint of the icon in its font
+    fn codepoint(&self) -> u32;
+
+    /// Return the Material glyph name of the icon, such as `"add_circle"`
+    fn name(&self) -> &'static str;
+
+    /// Return the raw data of the font containing the icon
+    fn icon_font(&self) -> &'static [u8];
+
+    /// Return the family name of the font containing the icon
+    fn family_name(&self) -> &'static str;
+
+    /// Return the style of the font containing the icon
+    fn style(&self) -> Style;
+
+    /// Return the icon as a `char`
+    fn char(&self) -> char {
+        // Safety: All codepoints are google-provided and should be valid
+        std::char::from_u32(self.codepoint()).unwrap_or(char::REPLACEMENT_CHARACTER)
+    }
+
+    /// Return the iced font needed to display the icon
+    #[cfg(feature = "iced")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "iced")))]
+    fn iced_font(&self) -> iced::Font {
+        iced::Font {
+            family: iced::font::Family::Name(self.family_name()),
+            ..Default::default()
+        }
+    }
+
+    /// Convert the icon to an iced Text widget
+    #[cfg(feature = "iced")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "iced")))]
+    fn into_text<'a, Theme>(
+        self,
+        font_size: impl Into<iced::Pixels>,
+    ) -> iced::widget::Text<'a, Theme>
+    where
+        Self: Sized,
+        Theme: iced::widget::text::Catalog,
+    {
+        iced::widget::Text::new(self.char())
+            .font(self.iced_font())
+            .size(font_size)
+    }
+    /// Return the iced font needed to display the icon at a variation  
+    /// Returns None unless the variation is the default, or was pre-baked by one of the instance features
+    #[cfg(feature = "iced")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "iced")))]
+    fn iced_font_with(&self, variation: &IconVariation) -> Option<iced::Font> {
+        let (family_name, _) = self.style().instance_font(variation)?;
+        Some(iced::Font {
+            family: iced::font::Family::Name(family_name),
+            ..Default::default()
+        })
+    }
+
+    /// Convert the icon to an iced Text widget, drawn at a variation  
+    /// Falls back to the default variation if the requested one was not pre-baked
+    #[cfg(feature = "iced")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "iced")))]
+    fn into_text_with<'a, Theme>(
+        self,
+        font_size: impl Into<iced::Pixels>,
+        variation: &IconVariation,
+    ) -> iced::widget::Text<'a, Theme>
+    where
+        Self: Sized,
+        Theme: iced::widget::text::Catalog,
+    {
+        let font = self
+            .iced_font_with(variation)
+            .unwrap_or_else(|| self.iced_font());
+        iced::widget::Text::new(self.char())
+            .font(font)
+            .size(font_size)
+    }
+}
+
+/// Error type for icon lookups
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IconError {
+    /// No icon exists with the given glyph name
+    UnknownName(String),
+
+    /// No icon exists at the given codepoint
+    UnknownCodepoint(u32),
+}
+impl std::fmt::Display for IconError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IconError::UnknownName(name) => write!(f, "Unknown icon name: {}", name),
+            IconError::UnknownCodepoint(codepoint) => {
+                write!(f, "Unknown icon codepoint: U+{:04X}", codepoint)
+            }
+        }
+    }
+}
+impl std::error::Error for IconError {}
 
 #[cfg(test)]
 mod test {
-    use crate::sharp::Icon;
+    use crate::IconName as Icon;
 
     #[test]
-    #[cfg(feature = "iced")]
+    #[cfg(all(feature = "iced", feature = "rounded", feature = "sharp"))]
     fn test_into_text() {
-        let icon = Icon::Add;
+        use crate::{MaterialIcon, Style};
+
+        let icon = crate::sharp::icon(Icon::Add);
         let _: iced::widget::Text = icon.into_text(24);
+        let _: iced::widget::Text = crate::sharp::Icon::Add.into_text(24);
+        let _: iced::Element<'_, ()> = crate::sharp::Icon::Add.into();
+
+        fn generic(icon: impl MaterialIcon) -> iced::widget::Text<'static> {
+            icon.into_text(24)
+        }
+        let _ = generic(Style::Rounded.icon(Icon::Add));
+        let _ = generic(crate::rounded::Icon::Add);
+    }
+
+    #[test]
+    #[cfg(feature = "iced")]
+    fn test_into_element() {
+        use crate::{MaterialIcon, Style};
+
+        // Every style's icons convert to the same widgets the per-style enums used to
+        for style in Style::ALL {
+            let icon = style.icon(Icon::Add);
+            assert_eq!(
+                icon.iced_font().family,
+                iced::font::Family::Name(style.family_name())
+            );
+
+            let _: iced::widget::Text = icon.into_text(24);
+            let _: iced::Element<'_, ()> = icon.into();
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "outlined", feature = "rounded", feature = "sharp"))]
+    fn test_instance_font() {
+        use crate::{IconVariation, Style};
+
+        for style in Style::ALL {
+            let (family_name, font) = style.instance_font(&IconVariation::DEFAULT).unwrap();
+            assert_eq!(family_name, style.family_name());
+            assert_eq!(font, style.icon_font());
+
+            let filled = style.instance_font(&IconVariation::FILLED);
+            assert_eq!(filled.is_some(), cfg!(feature = "filled"));
+            if let Some((family_name, _)) = filled {
+                assert!(family_name.ends_with(" Filled 400"));
+            }
+
+            let custom = IconVariation::DEFAULT.with_weight(250.0);
+            assert_eq!(style.instance_font(&custom), None);
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "outlined", feature = "rounded", feature = "sharp"))]
+    fn test_material_icon() {
+        use crate::{MaterialIcon, Style};
+
+        for style in Style::ALL {
+            let icon = Icon::Add.styled(*style);
+            assert_eq!(icon.name(), "add");
+            assert_eq!(icon.char() as u32, icon.codepoint());
+            assert_eq!(icon.style(), *style);
+            assert!(icon.family_name().starts_with("Material Symbols"));
+            assert!(!icon.icon_font().is_empty());
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "rounded", feature = "sharp"))]
+    fn test_styled_icon() {
+        use crate::{MaterialIcon, Style, StyledIcon};
+
+        let icon = StyledIcon::new(Icon::Add, Style::Sharp);
+        assert_eq!(icon, crate::sharp::icon(Icon::Add));
+        assert_eq!(char::from(icon), char::from(Icon::Add));
+        assert_eq!(icon.to_string(), Icon::Add.to_string());
+
+        let rounded = icon.with_style(Style::Rounded);
+        assert_eq!(rounded.name, Icon::Add);
+        assert_eq!(rounded.family_name(), "Material Symbols Rounded");
+    }
+
+    #[test]
+    #[cfg(all(feature = "outlined", feature = "rounded", feature = "sharp"))]
+    fn test_typed_icon() {
+        use crate::{outlined, rounded, sharp, MaterialIcon, Style, StyledIcon};
+
+        // Each style module's icons know their style, like the per-style enums did
+        assert_eq!(sharp::Icon::Add.style(), Style::Sharp);
+        assert_eq!(rounded::Icon::Add.family_name(), "Material Symbols Rounded");
+        assert_eq!(outlined::Icon::Add.icon_font(), outlined::icon_font());
+        assert_eq!(sharp::Icon::Add.icon_name(), Icon::Add);
+        assert_eq!(sharp::Icon::AccessAlarm, sharp::Icon::Alarm);
+        assert_eq!(StyledIcon::from(sharp::Icon::Add), sharp::icon(Icon::Add));
+
+        assert_eq!(sharp::Icon::from_name("10k"), Some(sharp::Icon::_10k));
+        assert_eq!("add".parse::<sharp::Icon>(), Ok(sharp::Icon::Add));
+        assert_eq!(
+            sharp::Icon::try_from(char::from(Icon::Add)),
+            Ok(sharp::Icon::Add)
+        );
+        assert_eq!(u32::from(sharp::Icon::Add), Icon::Add as u32);
+        assert_eq!(sharp::Icon::Add.to_string(), Icon::Add.to_string());
+        assert_eq!(sharp::Icon::ALL.len(), sharp::Icon::COUNT);
+        assert!(sharp::Icon::iter().eq(Icon::iter().map(sharp::Icon::from)));
+
+        // Icons convert between styles by name, and can still be matched on
+        for icon in sharp::Icon::iter() {
+            let outlined = outlined::Icon::from(icon);
+            let rounded = rounded::Icon::from(outlined);
+            assert_eq!(outlined.name(), icon.name());
+            assert_eq!(rounded.style(), Style::Rounded);
+            assert_eq!(sharp::Icon::from(rounded), icon);
+        }
+        assert!(matches!(
+            outlined::Icon::from(sharp::Icon::Add),
+            outlined::Icon::Add
+        ));
+    }
+
+    #[test]
+    fn test_ligature() {
+        assert_eq!(Icon::Add.ligature(), "add");
+        assert_eq!(Icon::_10k.name(), "_10k");
+        assert_eq!(Icon::_10k.ligature(), "10k");
     }
 
     #[test]
@@ -65,4 +930,134 @@ mod test {
         let _ = icon as u32;
         icon.to_string();
     }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(Icon::from_name("add"), Some(Icon::Add));
+        assert_eq!(Icon::from_name("add_circle"), Some(Icon::AddCircle));
+        assert_eq!(Icon::from_name("_10k"), Some(Icon::_10k));
+        assert_eq!(Icon::from_name("10k"), Some(Icon::_10k));
+        assert_eq!(Icon::from_name("not_an_icon"), None);
+        assert_eq!(Icon::from_name(""), None);
+
+        const TEN_K: Option<Icon> = Icon::from_name("10k");
+        assert_eq!(TEN_K, Some(Icon::_10k));
+
+        assert_eq!(Icon::from_name("access_alarm"), Some(Icon::Alarm));
+        assert_eq!(Icon::AccessAlarm, Icon::Alarm);
+        assert_eq!(Icon::Alarm.name(), "alarm");
+
+        assert_eq!("add".parse::<Icon>(), Ok(Icon::Add));
+        assert_eq!("10k".parse::<Icon>(), Ok(Icon::_10k));
+        assert_eq!(
+            "not_an_icon".parse::<Icon>(),
+            Err(crate::IconError::UnknownName("not_an_icon".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_similar_names() {
+        assert_eq!(Icon::similar_names("home")[0], "home");
+        assert!(Icon::similar_names("hmoe").contains(&"home"));
+        assert!(Icon::similar_names("not an icon at all").is_empty());
+    }
+
+    #[test]
+    fn test_name() {
+        const NAME: &str = Icon::AddCircle.name();
+        assert_eq!(NAME, "add_circle");
+
+        for (name, icon) in Icon::NAMES {
+            assert_eq!(icon.name(), *name);
+        }
+    }
+
+    #[test]
+    fn test_from_codepoint() {
+        assert_eq!(Icon::try_from(Icon::Add as u32), Ok(Icon::Add));
+        assert_eq!(
+            Icon::try_from(char::from(Icon::AddCircle)),
+            Ok(Icon::AddCircle)
+        );
+        assert_eq!(
+            Icon::try_from(0x10FFFF),
+            Err(crate::IconError::UnknownCodepoint(0x10FFFF))
+        );
+
+        for icon in Icon::iter() {
+            assert_eq!(Icon::from_codepoint(icon as u32), Some(icon));
+        }
+    }
+
+    #[test]
+    fn test_all() {
+        assert_eq!(Icon::ALL.len(), Icon::COUNT);
+        assert_eq!(Icon::iter().count(), Icon::COUNT);
+        assert_eq!(Icon::NAMES.len(), Icon::COUNT);
+        assert!(Icon::iter().any(|icon| icon == Icon::Add));
+    }
+
+    #[test]
+    fn test_renamed() {
+        // Old names resolve to the icon now at their codepoint, without being its name
+        for (name, icon) in Icon::RENAMED {
+            assert_eq!(Icon::from_name(name), Some(*icon));
+            assert_eq!(name.parse::<Icon>(), Ok(*icon));
+            assert_ne!(icon.name(), *name);
+        }
+    }
+
+    #[test]
+    fn test_renamed_lookup() {
+        // The font has no renamed icons yet, so check the lookup against a made-up table
+        let renamed = [("old_add", Icon::Add), ("old_home", Icon::Home)];
+        assert_eq!(
+            Icon::from_name_renamed("old_add", &renamed),
+            Some(Icon::Add)
+        );
+        assert_eq!(
+            Icon::from_name_renamed("old_home", &renamed),
+            Some(Icon::Home)
+        );
+        assert_eq!(Icon::from_name_renamed("old_search", &renamed), None);
+
+        // Current names still resolve, and the made-up names are not in the real table
+        assert_eq!(Icon::from_name_renamed("add", &renamed), Some(Icon::Add));
+        assert_eq!(Icon::from_name("old_add"), None);
+    }
+
+    #[test]
+    fn test_support_glyphs() {
+        assert_eq!(Icon::from_name(".notdef"), None);
+        assert_eq!(Icon::from_name("space"), None);
+        assert_eq!(
+            Icon::try_from('a'),
+            Err(crate::IconError::UnknownCodepoint('a' as u32))
+        );
+
+        let support = crate::SUPPORT_GLYPHS;
+        assert!(support.contains(&("space", ' ' as u32)));
+        assert!(support.contains(&("underscore", '_' as u32)));
+
+        // Both cases of a letter share a glyph, and both are listed
+        assert!(support.contains(&("a", 'A' as u32)));
+        assert!(support.contains(&("a", 'a' as u32)));
+        for (name, _) in support {
+            assert_eq!(Icon::from_name(name), None);
+        }
+    }
+
+    #[test]
+    fn test_name_table_sorted() {
+        assert!(Icon::NAMES.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(Icon::ALIASES.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(Icon::RENAMED.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn test_codepoint_table_sorted() {
+        assert!(Icon::CODEPOINTS
+            .windows(2)
+            .all(|w| (w[0].0 as u32) < (w[1].0 as u32)));
+    }
 }
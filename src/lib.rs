@@ -14,72 +14,354 @@
 //! - Rounded: [`material_design_icons::rounded`]
 //!
 //! ## Usage
-//! Load the font using the [`ICON_FONT`] constant.  
-//! Create an [`Icon`] object with the desired icon name.
+//! Every style shares the same set of icons, listed in [`IconName`].  
+//! Load the font for a style using its `icon_font()` function, or [`Style::icon_font`].  
+//! Pair an [`IconName`] with a [`Style`] to get a [`StyledIcon`], which knows which font it needs to be drawn with.
 //!
-//! [`Icon`] can be converted to `char` or [`String`] using the `From` trait.
+//! [`IconName`] can be converted to `char` or [`String`] using the `From` trait,  
+//! and recovered from a `char` or codepoint using the `TryFrom` trait.  
+//! Icons can also be looked up by their Material glyph name using [`std::str::FromStr`] or `IconName::from_name`,  
+//! and `IconName::name` returns that name again.  
+//! Every icon can be listed with `IconName::ALL` or `IconName::iter()`.
 //!
-//! If the feature `iced` is enabled, [`Icon`] also implements the `Into<iced::Element>` trait.  
-//! - You will need to include `.font(ICON_FONT)` when creating your iced application.
+//! Some icons can also be spelled out by other names, such as `access_alarm` for `alarm`.  
+//! These aliases are available as constants like `IconName::AccessAlarm`, and are accepted by `IconName::from_name`.  
+//! Names that newer releases of the font no longer use are kept as deprecated constants, pointing to the renamed icon.
+//!
+//! [`StyledIcon`] implements the [`MaterialIcon`] trait, which can be used to accept any icon generically.  
+//! Its style can be switched at runtime using `StyledIcon::with_style`.
+//!
+//! Each style module also provides its own `Icon` type, such as `sharp::Icon`, with a constant for every icon.  
+//! These know their style, so they implement [`MaterialIcon`] and can be drawn directly, and convert between styles using `From`.  
+//! They wrap an [`IconName`] rather than being enums themselves, so use `u32::from` where `as u32` was used before.
+//!
+//! ## Features
+//! Each style's font is only embedded in your binary if its feature is enabled.  
+//! The `outlined`, `rounded` and `sharp` features are all enabled by default;
+//! disable default features and pick the styles you need to leave the others out.
+//!
+//! The `compressed` feature embeds the fonts brotli-compressed, and decompresses each one the first time
+//! its `icon_font()` function is called. This makes the binary much smaller, at the cost of a short delay on first use.  
+//! The uncompressed `ICON_FONT` constants are still available, but using one embeds that font uncompressed as well.
+//!
+//! The `subset` feature cuts each embedded font down to a fixed set of icons at build time.
+//! List the icons to keep in the `MATERIAL_DESIGN_ICONS_SUBSET` environment variable, separated by commas or whitespace.
+//! Names are matched as in [`IconName::from_name`], except that the old names in [`IconName::RENAMED`] are not accepted:
+//! ```text
+//! MATERIAL_DESIGN_ICONS_SUBSET="add, delete, home, 10k" cargo build --features subset
+//! ```
+//! - The icons keep their codepoints, so [`IconName`] still works as usual - but any other icon will not be drawn.
+//! - The subset fonts are static, and no longer contain the variation tables - but the ligatures that spell out the kept icons still work.
+//! - If the variable is not set, the fonts are embedded whole.
+//! - The build fails if the list is empty, or names anything that is not an icon, such as a letter.
+//!
+//! The `filled`, `bold` and `filled-bold` features bake static instances of each enabled style at build time,
+//! at [`IconVariation::FILLED`], [`IconVariation::BOLD`] and [`IconVariation::FILLED_BOLD`].  
+//! Each is embedded in a module of its style, such as `sharp::filled`, with its own family name like "Material Symbols Sharp Filled 400".
+//! Use `Style::instance_font` to look them up by variation.
+//!
+//! The `serde` feature implements `Serialize` and `Deserialize` for [`IconName`], [`Style`] and [`StyledIcon`].
+//! Icons are serialized by their glyph name, such as `"add_circle"`; see the `serde` module to serialize them by codepoint instead.
+//!
+//! The `png` feature enables the `parser` feature, and adds `Font::render_png` to encode rendered icons as PNG images.
+//!
+//! If the feature `iced` is enabled, [`StyledIcon`] and each style's `Icon` also implement the `Into<iced::Element>` trait.  
+//! - You will need to include `.font(icon_font())` when creating your iced application.
+//! - To draw filled or bold icons, enable one of the instance features, load its `icon_font()` too, and use `into_text_with`.
 //!
 //! ## Examples
 //!
 //! ```rust
-//! use material_design_icons::sharp::Icon;
+//! use material_design_icons::IconName as Icon;
 //! let icon = Icon::Add;
 //! let char = char::from(icon);
 //! let codepoint = icon as u32;
 //! let string = icon.to_string();
+//!
+//! let icon: Icon = "add_circle".parse().unwrap();
+//! assert_eq!(Icon::from_name("add_circle"), Some(icon));
+//! assert_eq!(icon.name(), "add_circle");
+//! ```
+//!
+//! Icons can be paired with a style, which can be changed at runtime.
+//! ```rust
+//! # #[cfg(all(feature = "sharp", feature = "rounded"))]
+//! # fn main() {
+//! use material_design_icons::{IconName, Style};
+//! let icon = IconName::Add.styled(Style::Sharp);
+//! let icon = icon.with_style(Style::Rounded);
+//! let font = icon.style.icon_font();
+//! # }
+//! # #[cfg(not(all(feature = "sharp", feature = "rounded")))]
+//! # fn main() {}
+//! ```
+//!
+//! The [`icon!`] macro checks names at compile time, and accepts them exactly as written on fonts.google.com.
+//! ```rust
+//! # #[cfg(feature = "sharp")]
+//! # fn main() {
+//! use material_design_icons::{icon, IconName};
+//! const ICON: IconName = icon!("10k");
+//! let icon = icon!(sharp, "add_circle");
+//! # }
+//! # #[cfg(not(feature = "sharp"))]
+//! # fn main() {}
 //! ```
 //!
 //! If you use the `parser` feature, you can load the font and extract the icon data.
-//! ```ignore
+//! ```no_run
+//! # #[cfg(all(feature = "parser", feature = "sharp"))]
+//! # fn main() {
 //! use material_design_icons::sharp::Icon;
 //! use material_design_icons::font::Font;
 //! let font = Font::new_sharp().unwrap();
 //!
-//! let index = font.index_of(Icon::Add as u32).unwrap();
+//! let index = font.index_of(u32::from(Icon::Add)).unwrap();
 //! let name = font.glyph_name(index).unwrap();
 //! let bitmap = font.bitmap_for(index).unwrap();
+//!
+//! // Names are also resolved through the font's ligatures, including aliases
+//! let index = font.lookup_ligature("home_filled").unwrap();
+//! # }
+//! # #[cfg(not(all(feature = "parser", feature = "sharp")))]
+//! # fn main() {}
+//! ```
+//!
+//! Fonts can also own their data, with `Font::from_vec`, `Font::from_arc` or `Font::from_path`.  
+//! An owned font is `Send + Sync`, as is a `FontMapper` built from it, so it can be parsed once and shared in an `Arc`.
+//! ```no_run
+//! # #[cfg(feature = "parser")]
+//! # fn main() {
+//! use std::sync::Arc;
+//! use material_design_icons::font::{Font, FontMapper};
+//! let font = Font::from_path("MaterialSymbolsSharp.ttf").unwrap();
+//! let mapper = Arc::new(FontMapper::new(&font).unwrap());
+//! # }
+//! # #[cfg(not(feature = "parser"))]
+//! # fn main() {}
+//! ```
+//!
+//! The fonts are variable, and can be drawn filled, bolder or lighter with an [`IconVariation`].
+//! With the `parser` feature, variations can be checked against the font's axes:
+//! ```no_run
+//! # #[cfg(all(feature = "parser", feature = "sharp"))]
+//! # fn main() {
+//! use material_design_icons::{font::Font, IconVariation};
+//! let font = Font::new_sharp().unwrap();
+//!
+//! let filled = IconVariation::DEFAULT.with_fill(1.0).with_weight(700.0);
+//! font.validate_variation(&filled).unwrap();
+//!
+//! // The fonts have no bitmaps, but the outline of any icon can be read as a vector path
+//! let index = font.index_of(material_design_icons::IconName::Add as u32).unwrap();
+//! let path = font.outline(index, &filled).unwrap();
+//!
+//! // Metrics for laying icons out next to text, as stored in the font or adjusted for a variation
+//! let metrics = font.metrics_at(index, &filled).unwrap();
+//! let line = font.vertical_metrics().unwrap();
+//! # }
+//! # #[cfg(not(all(feature = "parser", feature = "sharp")))]
+//! # fn main() {}
+//! ```
+//!
+//! Icons can also be written out as SVG images, at any size and variation.
+//! ```no_run
+//! # #[cfg(all(feature = "png", feature = "sharp"))]
+//! # fn main() {
+//! use material_design_icons::{font::{Font, SvgOptions}, IconName, IconVariation};
+//! let font = Font::new_sharp().unwrap();
+//!
+//! let options = SvgOptions::default().with_size(48.0).with_variation(IconVariation::FILLED);
+//! let svg = font.to_svg(IconName::Home, &options).unwrap();
+//!
+//! // Or rendered to pixels on the CPU, as a coverage mask or a premultiplied RGBA image
+//! let pixmap = font.render_rgba(IconName::Home, 48, [0x1a, 0x73, 0xe8, 0xff], &IconVariation::FILLED).unwrap();
+//!
+//! // With the `png` feature, as a file extension and image data - the same shape as `BitmapExt::export`
+//! let (extension, png) = font.render_png(IconName::Home, 48, [0x1a, 0x73, 0xe8, 0xff], &IconVariation::FILLED).unwrap();
+//! # }
+//! # #[cfg(not(all(feature = "png", feature = "sharp")))]
+//! # fn main() {}
+//! ```
+//!
+//! Any variation can also be baked into a static font with its own family name, for renderers without variable font support.
+//! The `parse` binary does the same from the command line, with `parse instance sharp --fill 1 --weight 700`.
+//! ```no_run
+//! # #[cfg(all(feature = "parser", feature = "sharp"))]
+//! # fn main() {
+//! use material_design_icons::{font::Font, IconVariation};
+//! let font = Font::new_sharp().unwrap();
+//!
+//! let variation = IconVariation::FILLED_BOLD;
+//! let family_name = variation.instance_family_name("Material Symbols Sharp");
+//! let ttf = font.instance(&variation, &family_name).unwrap();
+//! # }
+//! # #[cfg(not(all(feature = "parser", feature = "sharp")))]
+//! # fn main() {}
 //! ```
 //!
 //! If you are using `iced`, you can convert the icon to a `Text` widget.
-//! ```ignore
-//! use material_design_icons::sharp::Icon;
+//! ```rust
+//! # #[cfg(all(feature = "iced", feature = "sharp"))]
+//! # fn main() {
+//! use material_design_icons::{sharp, IconName};
 //!
-//! let icon = Icon::Add;
-//! let text = icon.into_text(24);
+//! let text: iced::widget::Text = sharp::Icon::Add.into_text(24);
+//!
+//! // Or pair an icon with a style that can be changed at runtime
+//! let icon = sharp::icon(IconName::Add);
+//! let text: iced::widget::Text = icon.into_text(24);
+//! # }
+//! # #[cfg(not(all(feature = "iced", feature = "sharp")))]
+//! # fn main() {}
 //! ```
 //!
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 /// Autogenerated glyph data
+#[macro_use]
 mod glyphs;
+pub use glyphs::{IconName, SUPPORT_GLYPHS};
 
 #[macro_use]
+mod embed;
+
 mod icon;
+pub use icon::{IconError, MaterialIcon, Style, StyleMarker, StyledIcon, TypedIcon};
+
+mod variation;
+pub use variation::{Axis, AxisRange, IconVariation, VariationError};
+
+#[doc(hidden)]
+pub mod macros;
 
 #[cfg(feature = "parser")]
 #[cfg_attr(docsrs, doc(cfg(feature = "parser")))]
 pub mod font;
 
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod serde;
+
 /// Google Material Design Icons in the "Sharp" style.
+#[cfg(feature = "sharp")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sharp")))]
 pub mod sharp {
-    pub const ICON_FONT: &[u8] = include_bytes!("fonts/MaterialSymbolsSharp.ttf");
-    pub use super::glyphs::Sharp as Icon;
-    impl_icon!(Icon, "Material Symbols Sharp");
+    use crate::{IconName, Style, StyledIcon};
+
+    embed_font!("MaterialSymbolsSharp.ttf");
+    embed_instances!("MaterialSymbolsSharp", "Material Symbols Sharp");
+    pub const STYLE: Style = Style::Sharp;
+    pub use crate::SUPPORT_GLYPHS;
+
+    /// Marks a [`TypedIcon`](crate::TypedIcon) as drawn in the "Sharp" style
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Sharp;
+    impl crate::StyleMarker for Sharp {
+        const STYLE: Style = STYLE;
+    }
+
+    /// Google Material Design Icons in the "Sharp" style, such as `sharp::Icon::Add`
+    pub type Icon = crate::TypedIcon<Sharp>;
+
+    #[cfg(feature = "outlined")]
+    impl From<crate::outlined::Icon> for Icon {
+        fn from(value: crate::outlined::Icon) -> Self {
+            value.with_style()
+        }
+    }
+
+    #[cfg(feature = "rounded")]
+    impl From<crate::rounded::Icon> for Icon {
+        fn from(value: crate::rounded::Icon) -> Self {
+            value.with_style()
+        }
+    }
+
+    /// Pair an icon with the "Sharp" style, so that it can be drawn
+    pub const fn icon(name: IconName) -> StyledIcon {
+        STYLE.icon(name)
+    }
 }
 
 /// Google Material Design Icons in the "Outlined" style.
+#[cfg(feature = "outlined")]
+#[cfg_attr(docsrs, doc(cfg(feature = "outlined")))]
 pub mod outlined {
-    pub const ICON_FONT: &[u8] = include_bytes!("fonts/MaterialSymbolsOutlined.ttf");
-    pub use super::glyphs::Outlined as Icon;
-    impl_icon!(Icon, "Material Symbols Outlined");
+    use crate::{IconName, Style, StyledIcon};
+
+    embed_font!("MaterialSymbolsOutlined.ttf");
+    embed_instances!("MaterialSymbolsOutlined", "Material Symbols Outlined");
+    pub const STYLE: Style = Style::Outlined;
+    pub use crate::SUPPORT_GLYPHS;
+
+    /// Marks a [`TypedIcon`](crate::TypedIcon) as drawn in the "Outlined" style
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Outlined;
+    impl crate::StyleMarker for Outlined {
+        const STYLE: Style = STYLE;
+    }
+
+    /// Google Material Design Icons in the "Outlined" style, such as `outlined::Icon::Add`
+    pub type Icon = crate::TypedIcon<Outlined>;
+
+    #[cfg(feature = "rounded")]
+    impl From<crate::rounded::Icon> for Icon {
+        fn from(value: crate::rounded::Icon) -> Self {
+            value.with_style()
+        }
+    }
+
+    #[cfg(feature = "sharp")]
+    impl From<crate::sharp::Icon> for Icon {
+        fn from(value: crate::sharp::Icon) -> Self {
+            value.with_style()
+        }
+    }
+
+    /// Pair an icon with the "Outlined" style, so that it can be drawn
+    pub const fn icon(name: IconName) -> StyledIcon {
+        STYLE.icon(name)
+    }
 }
 
 /// Google Material Design Icons in the "Rounded" style.
+#[cfg(feature = "rounded")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rounded")))]
 pub mod rounded {
-    pub const ICON_FONT: &[u8] = include_bytes!("fonts/MaterialSymbolsRounded.ttf");
-    pub use super::glyphs::Rounded as Icon;
-    impl_icon!(Icon, "Material Symbols Rounded");
+    use crate::{IconName, Style, StyledIcon};
+
+    embed_font!("MaterialSymbolsRounded.ttf");
+    embed_instances!("MaterialSymbolsRounded", "Material Symbols Rounded");
+    pub const STYLE: Style = Style::Rounded;
+    pub use crate::SUPPORT_GLYPHS;
+
+    /// Marks a [`TypedIcon`](crate::TypedIcon) as drawn in the "Rounded" style
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Rounded;
+    impl crate::StyleMarker for Rounded {
+        const STYLE: Style = STYLE;
+    }
+
+    /// Google Material Design Icons in the "Rounded" style, such as `rounded::Icon::Add`
+    pub type Icon = crate::TypedIcon<Rounded>;
+
+    #[cfg(feature = "outlined")]
+    impl From<crate::outlined::Icon> for Icon {
+        fn from(value: crate::outlined::Icon) -> Self {
+            value.with_style()
+        }
+    }
+
+    #[cfg(feature = "sharp")]
+    impl From<crate::sharp::Icon> for Icon {
+        fn from(value: crate::sharp::Icon) -> Self {
+            value.with_style()
+        }
+    }
+
+    /// Pair an icon with the "Rounded" style, so that it can be drawn
+    pub const fn icon(name: IconName) -> StyledIcon {
+        STYLE.icon(name)
+    }
 }
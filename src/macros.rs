@@ -0,0 +1,164 @@
+//! Compile-time support for the [`icon!`](crate::icon!) macro
+//!
+//! Not part of the public API - the items here are only public so that the macro can reach them
+//!
+use crate::IconName;
+
+/// Look up an icon by its Material glyph name at compile time
+///
+/// The name can be written exactly as it appears on [fonts.google.com](https://fonts.google.com/icons),
+/// such as `"add_circle"` or `"10k"`, instead of working out the enum variant by hand.  
+/// An unknown name is a compile error, which suggests the nearest icon names.
+///
+/// - `icon!("add_circle")` resolves to an [`IconName`]
+/// - `icon!(sharp, "add_circle")` resolves to a [`StyledIcon`](crate::StyledIcon) in the given style
+/// - `icon!(char, "add_circle")` resolves to the icon's `char`
+///
+#[cfg_attr(feature = "sharp", doc = "```rust")]
+#[cfg_attr(not(feature = "sharp"), doc = "```ignore")]
+/// use material_design_icons::{icon, IconName};
+///
+/// const ADD: IconName = icon!("add_circle");
+/// assert_eq!(ADD, IconName::AddCircle);
+/// assert_eq!(icon!("10k"), IconName::_10k);
+///
+/// let icon = icon!(sharp, "add_circle");
+/// assert_eq!(icon.name, IconName::AddCircle);
+///
+/// let c: char = icon!(char, "add_circle");
+/// assert_eq!(c, char::from(IconName::AddCircle));
+/// ```
+///
+/// ```compile_fail
+/// // error: unknown icon name `add_cirlce` - did you mean `add_circle`?
+/// let icon = material_design_icons::icon!("add_cirlce");
+/// ```
+#[macro_export]
+macro_rules! icon {
+    (char, $name:literal) => {
+        const { $crate::macros::icon_char($crate::macros::lookup($name)) }
+    };
+
+    ($style:ident, $name:literal) => {
+        const { $crate::$style::icon($crate::macros::lookup($name)) }
+    };
+
+    ($name:literal) => {
+        const { $crate::macros::lookup($name) }
+    };
+}
+
+/// Resolve an icon name for the [`icon!`](crate::icon!) macro, failing compilation if it is unknown  
+/// Accepts the same names as [`IconName::from_name`]
+pub const fn lookup(name: &str) -> IconName {
+    match IconName::from_name(name) {
+        Some(icon) => icon,
+        None => {
+            let message = unknown_name_message(name);
+            panic!("{}", message.as_str())
+        }
+    }
+}
+
+/// Return the `char` for an icon, for the [`icon!`](crate::icon!) macro
+pub const fn icon_char(icon: IconName) -> char {
+    match char::from_u32(icon as u32) {
+        Some(c) => c,
+        None => panic!("icon codepoint is not a valid char"),
+    }
+}
+
+/// Build the compile error for an unknown icon name, with the nearest names as suggestions
+const fn unknown_name_message(name: &str) -> Message {
+    let message = Message::new()
+        .push("unknown icon name `")
+        .push(name)
+        .push("`");
+
+    let suggestions = IconName::nearest_names(name);
+    let Some(first) = suggestions[0] else {
+        return message.push(" - see https://fonts.google.com/icons for the list of icons");
+    };
+
+    let mut message = message.push(" - did you mean `").push(first).push("`");
+    let mut i = 1;
+    while i < suggestions.len() {
+        if let Some(suggestion) = suggestions[i] {
+            message = message.push(", `").push(suggestion).push("`");
+        }
+        i += 1;
+    }
+
+    message.push("?")
+}
+
+/// A fixed-size string, for building error messages in const code  
+/// Text that does not fit is left out, rather than cut off mid-character
+struct Message {
+    buffer: [u8; 256],
+    len: usize,
+}
+impl Message {
+    const fn new() -> Self {
+        Self {
+            buffer: [0; 256],
+            len: 0,
+        }
+    }
+
+    const fn push(mut self, text: &str) -> Self {
+        let text = text.as_bytes();
+        if self.len + text.len() > self.buffer.len() {
+            return self;
+        }
+
+        let mut i = 0;
+        while i < text.len() {
+            self.buffer[self.len + i] = text[i];
+            i += 1;
+        }
+        self.len += text.len();
+        self
+    }
+
+    const fn as_str(&self) -> &str {
+        match std::str::from_utf8(self.buffer.split_at(self.len).0) {
+            Ok(text) => text,
+            Err(_) => "unknown icon name",
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::IconName as Icon;
+
+    #[test]
+    fn test_icon_macro() {
+        assert_eq!(icon!("add_circle"), Icon::AddCircle);
+        assert_eq!(icon!("10k"), Icon::_10k);
+        assert_eq!(icon!("_10k"), Icon::_10k);
+        assert_eq!(icon!(char, "add"), char::from(Icon::Add));
+        assert_eq!(icon!("access_alarm"), Icon::Alarm);
+    }
+
+    #[test]
+    #[cfg(feature = "sharp")]
+    fn test_styled_icon_macro() {
+        use crate::Style;
+
+        const ICON: crate::StyledIcon = icon!(sharp, "add_circle");
+        assert_eq!(ICON, Icon::AddCircle.styled(Style::Sharp));
+    }
+
+    #[test]
+    fn test_unknown_name_message() {
+        let message = super::unknown_name_message("add_cirlce");
+        assert!(message
+            .as_str()
+            .starts_with("unknown icon name `add_cirlce` - did you mean `add_circle`"));
+
+        let message = super::unknown_name_message("not an icon at all");
+        assert!(message.as_str().contains("fonts.google.com/icons"));
+    }
+}
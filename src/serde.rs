@@ -0,0 +1,190 @@
+//! Serde support for icons
+//!
+//! With the `serde` feature enabled, [`IconName`] is serialized by its Material glyph name, such as `"add_circle"`,
+//! and [`Style`] and [`StyledIcon`] derive `Serialize` and `Deserialize` on top of it.
+//!
+//! The modules here can be used with `#[serde(with = "...")]` to serialize icons by codepoint instead:
+//! ```rust
+//! use material_design_icons::{IconName, StyledIcon};
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Settings {
+//!     #[serde(with = "material_design_icons::serde::codepoint")]
+//!     icon: IconName,
+//!
+//!     #[serde(with = "material_design_icons::serde::styled_codepoint")]
+//!     styled: StyledIcon,
+//! }
+//! ```
+//!
+use crate::{IconError, IconName, Style, StyledIcon};
+use ::serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+impl Serialize for IconName {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for IconName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(NameVisitor {
+            renamed: IconName::RENAMED,
+        })
+    }
+}
+
+/// Visitor for icons serialized by name, accepting the old names in `renamed`
+struct NameVisitor {
+    renamed: &'static [(&'static str, IconName)],
+}
+impl de::Visitor<'_> for NameVisitor {
+    type Value = IconName;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "a Material Symbols icon name, such as \"add_circle\"")
+    }
+
+    fn visit_str<E: de::Error>(self, name: &str) -> Result<Self::Value, E> {
+        if let Some(icon) = IconName::from_name_renamed(name, self.renamed) {
+            return Ok(icon);
+        }
+
+        let suggestions = IconName::similar_names(name);
+        if suggestions.is_empty() {
+            Err(E::custom(format!(
+                "unknown icon name `{name}`, see https://fonts.google.com/icons for the list of icons"
+            )))
+        } else {
+            Err(E::custom(format!(
+                "unknown icon name `{name}`, did you mean `{}`?",
+                suggestions.join("`, `")
+            )))
+        }
+    }
+}
+
+/// Serialize an [`IconName`] as its codepoint, such as `57669`, instead of its name
+pub mod codepoint {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(icon: &IconName, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(*icon as u32)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<IconName, D::Error> {
+        let codepoint = u32::deserialize(deserializer)?;
+        IconName::try_from(codepoint).map_err(de::Error::custom)
+    }
+}
+
+/// Serialize a [`StyledIcon`] with its codepoint, such as `{ "codepoint": 57669, "style": "sharp" }`, instead of its name
+pub mod styled_codepoint {
+    use super::*;
+
+    /// The serialized form of the icon
+    #[derive(Serialize, Deserialize)]
+    struct StyledCodepoint {
+        codepoint: u32,
+        style: Style,
+    }
+
+    pub fn serialize<S: Serializer>(icon: &StyledIcon, serializer: S) -> Result<S::Ok, S::Error> {
+        StyledCodepoint {
+            codepoint: icon.name as u32,
+            style: icon.style,
+        }
+        .serialize(serializer)
+    }
+
+    // With no styles enabled, `Style` is uninhabited and the result can never be built
+    #[allow(unreachable_code)]
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<StyledIcon, D::Error> {
+        let StyledCodepoint { codepoint, style } = StyledCodepoint::deserialize(deserializer)?;
+        let name: IconName = codepoint
+            .try_into()
+            .map_err(|e: IconError| de::Error::custom(e))?;
+        Ok(StyledIcon::new(name, style))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::NameVisitor;
+    use crate::IconName as Icon;
+    use serde::Deserializer;
+
+    #[test]
+    fn test_icon_name() {
+        let json = serde_json::to_string(&Icon::AddCircle).unwrap();
+        assert_eq!(json, "\"add_circle\"");
+        assert_eq!(
+            serde_json::from_str::<Icon>(&json).unwrap(),
+            Icon::AddCircle
+        );
+
+        // Names are read the same way as `IconName::from_name`
+        assert_eq!(serde_json::from_str::<Icon>("\"10k\"").unwrap(), Icon::_10k);
+
+        let err = serde_json::from_str::<Icon>("\"homr\"").unwrap_err();
+        assert!(err.to_string().contains("did you mean `home`"));
+    }
+
+    #[test]
+    fn test_renamed() {
+        // The font has no renamed icons yet, so deserialize against a made-up table
+        let renamed = NameVisitor {
+            renamed: &[("old_add", Icon::Add)],
+        };
+        let mut deserializer = serde_json::Deserializer::from_str("\"old_add\"");
+        let icon = deserializer.deserialize_str(renamed).unwrap();
+        assert_eq!(icon, Icon::Add);
+
+        // Renamed icons are serialized by their new name
+        assert_eq!(serde_json::to_string(&icon).unwrap(), "\"add\"");
+    }
+
+    #[test]
+    #[cfg(feature = "sharp")]
+    fn test_styled_icon() {
+        use crate::{Style, StyledIcon};
+
+        let icon = StyledIcon::new(Icon::Add, Style::Sharp);
+        let json = serde_json::to_string(&icon).unwrap();
+        assert_eq!(json, r#"{"name":"add","style":"sharp"}"#);
+        assert_eq!(serde_json::from_str::<StyledIcon>(&json).unwrap(), icon);
+    }
+
+    #[test]
+    #[cfg(feature = "sharp")]
+    fn test_codepoint() {
+        use crate::{Style, StyledIcon};
+
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Settings {
+            #[serde(with = "crate::serde::codepoint")]
+            icon: Icon,
+
+            #[serde(with = "crate::serde::styled_codepoint")]
+            styled: StyledIcon,
+        }
+
+        let settings = Settings {
+            icon: Icon::Add,
+            styled: StyledIcon::new(Icon::Add, Style::Sharp),
+        };
+        let json = serde_json::to_string(&settings).unwrap();
+        let codepoint = Icon::Add as u32;
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"icon":{codepoint},"styled":{{"codepoint":{codepoint},"style":"sharp"}}}}"#
+            )
+        );
+        assert_eq!(serde_json::from_str::<Settings>(&json).unwrap(), settings);
+
+        assert!(serde_json::from_str::<Settings>(
+            r#"{"icon":1,"styled":{"codepoint":1,"style":"sharp"}}"#
+        )
+        .is_err());
+    }
+}
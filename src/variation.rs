@@ -0,0 +1,313 @@
+//! Settings for the variable axes of the Material Symbols fonts
+//!
+//! Every style of the font can be drawn filled or unfilled, at a range of weights and grades,
+//! and tuned for a range of optical sizes. [`IconVariation`] picks a point on each of those axes.
+//!
+
+/// A variable axis of the Material Symbols fonts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    /// `FILL` - 0 for outlined icons, 1 for filled ones
+    Fill,
+
+    /// `wght` - stroke weight, from 100 (thin) to 700 (bold)
+    Weight,
+
+    /// `GRAD` - fine-grained emphasis, from -50 (low emphasis) to 200 (high emphasis)
+    Grade,
+
+    /// `opsz` - the size in pixels the icon is tuned for, from 20 to 48
+    OpticalSize,
+}
+impl Axis {
+    /// Every axis, in the order of the fields of [`IconVariation`]
+    pub const ALL: &'static [Self] = &[Self::Fill, Self::Weight, Self::Grade, Self::OpticalSize];
+
+    /// Return the 4-byte OpenType tag of the axis, such as `b"wght"`
+    pub const fn tag(self) -> [u8; 4] {
+        match self {
+            Self::Fill => *b"FILL",
+            Self::Weight => *b"wght",
+            Self::Grade => *b"GRAD",
+            Self::OpticalSize => *b"opsz",
+        }
+    }
+
+    /// Look up an axis by its OpenType tag, as stored in the `fvar` table
+    pub fn from_tag(tag: u32) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|axis| u32::from_be_bytes(axis.tag()) == tag)
+    }
+}
+impl std::fmt::Display for Axis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", String::from_utf8_lossy(&self.tag()))
+    }
+}
+
+/// The range of values a font supports on one of its axes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AxisRange {
+    pub axis: Axis,
+    pub min: f32,
+    pub default: f32,
+    pub max: f32,
+}
+impl AxisRange {
+    /// Check if a value is within the range
+    pub fn contains(&self, value: f32) -> bool {
+        (self.min..=self.max).contains(&value)
+    }
+}
+
+/// A point on each of the variable axes of the font  
+/// The default is the font's default instance - unfilled, weight 400, grade 0, optical size 24
+///
+/// ```rust
+/// use material_design_icons::IconVariation;
+/// let bold = IconVariation::DEFAULT.with_fill(1.0).with_weight(700.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct IconVariation {
+    /// `FILL` axis - 0 for outlined icons, 1 for filled ones
+    pub fill: f32,
+
+    /// `wght` axis - stroke weight, from 100 (thin) to 700 (bold)
+    pub weight: f32,
+
+    /// `GRAD` axis - fine-grained emphasis, from -50 (low emphasis) to 200 (high emphasis)
+    pub grade: f32,
+
+    /// `opsz` axis - the size in pixels the icon is tuned for, from 20 to 48
+    pub optical_size: f32,
+}
+impl IconVariation {
+    /// The default instance of the fonts
+    pub const DEFAULT: Self = Self {
+        fill: 0.0,
+        weight: 400.0,
+        grade: 0.0,
+        optical_size: 24.0,
+    };
+
+    /// Filled icons, at the default weight
+    pub const FILLED: Self = Self::DEFAULT.with_fill(1.0);
+
+    /// Unfilled icons, at the heaviest weight
+    pub const BOLD: Self = Self::DEFAULT.with_weight(700.0);
+
+    /// Filled icons, at the heaviest weight
+    pub const FILLED_BOLD: Self = Self::FILLED.with_weight(700.0);
+
+    /// Return a copy with a different `FILL` value
+    pub const fn with_fill(self, fill: f32) -> Self {
+        Self { fill, ..self }
+    }
+
+    /// Return a copy with a different `wght` value
+    pub const fn with_weight(self, weight: f32) -> Self {
+        Self { weight, ..self }
+    }
+
+    /// Return a copy with a different `GRAD` value
+    pub const fn with_grade(self, grade: f32) -> Self {
+        Self { grade, ..self }
+    }
+
+    /// Return a copy with a different `opsz` value
+    pub const fn with_optical_size(self, optical_size: f32) -> Self {
+        Self {
+            optical_size,
+            ..self
+        }
+    }
+
+    /// Return the value for one of the axes
+    pub const fn value(&self, axis: Axis) -> f32 {
+        match axis {
+            Axis::Fill => self.fill,
+            Axis::Weight => self.weight,
+            Axis::Grade => self.grade,
+            Axis::OpticalSize => self.optical_size,
+        }
+    }
+
+    /// Return the family name of a static instance of a font at this variation  
+    /// The weight is always included, while the other axes are only named when they are not at their default
+    ///
+    /// ```rust
+    /// use material_design_icons::IconVariation;
+    /// let name = IconVariation::FILLED_BOLD.instance_family_name("Material Symbols Sharp");
+    /// assert_eq!(name, "Material Symbols Sharp Filled 700");
+    /// ```
+    pub fn instance_family_name(&self, family: &str) -> String {
+        let mut name = family.to_string();
+        if self.fill == 1.0 {
+            name += " Filled";
+        } else if self.fill != Self::DEFAULT.fill {
+            name += &format!(" Fill {}", self.fill);
+        }
+
+        name += &format!(" {}", self.weight);
+        if self.grade != Self::DEFAULT.grade {
+            name += &format!(" Grade {}", self.grade);
+        }
+        if self.optical_size != Self::DEFAULT.optical_size {
+            name += &format!(" Opsz {}", self.optical_size);
+        }
+
+        name
+    }
+
+    /// Check the variation against the axis ranges of a font  
+    /// Axes the font does not have can only be left at their default value
+    ///
+    /// With the `parser` feature, the ranges can be read with `font::Font::axis_ranges`
+    pub fn validate(&self, ranges: &[AxisRange]) -> Result<(), VariationError> {
+        for &axis in Axis::ALL {
+            let value = self.value(axis);
+            match ranges.iter().find(|range| range.axis == axis) {
+                Some(range) if !range.contains(value) => {
+                    return Err(VariationError::OutOfRange {
+                        axis,
+                        value,
+                        min: range.min,
+                        max: range.max,
+                    });
+                }
+
+                None if value != Self::DEFAULT.value(axis) => {
+                    return Err(VariationError::UnsupportedAxis(axis));
+                }
+
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+impl Default for IconVariation {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Error type for invalid icon variations
+#[derive(Debug, Clone, PartialEq)]
+pub enum VariationError {
+    /// The value is outside the range the font supports for the axis
+    OutOfRange {
+        axis: Axis,
+        value: f32,
+        min: f32,
+        max: f32,
+    },
+
+    /// The font does not have the axis at all
+    UnsupportedAxis(Axis),
+}
+impl std::fmt::Display for VariationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VariationError::OutOfRange {
+                axis,
+                value,
+                min,
+                max,
+            } => write!(
+                f,
+                "Value {} for axis {} is outside the supported range {}..={}",
+                value, axis, min, max
+            ),
+            VariationError::UnsupportedAxis(axis) => {
+                write!(f, "The font has no {} axis", axis)
+            }
+        }
+    }
+}
+impl std::error::Error for VariationError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// The ranges shared by every style of the font
+    const RANGES: &[AxisRange] = &[
+        AxisRange {
+            axis: Axis::Fill,
+            min: 0.0,
+            default: 0.0,
+            max: 1.0,
+        },
+        AxisRange {
+            axis: Axis::Weight,
+            min: 100.0,
+            default: 400.0,
+            max: 700.0,
+        },
+    ];
+
+    #[test]
+    fn test_axis_tags() {
+        for &axis in Axis::ALL {
+            assert_eq!(Axis::from_tag(u32::from_be_bytes(axis.tag())), Some(axis));
+        }
+        assert_eq!(Axis::Weight.to_string(), "wght");
+        assert_eq!(Axis::from_tag(u32::from_be_bytes(*b"wdth")), None);
+    }
+
+    #[test]
+    fn test_validate() {
+        let variation = IconVariation::default().with_fill(1.0).with_weight(700.0);
+        assert_eq!(variation.validate(RANGES), Ok(()));
+
+        let variation = IconVariation::DEFAULT.with_weight(900.0);
+        assert_eq!(
+            variation.validate(RANGES),
+            Err(VariationError::OutOfRange {
+                axis: Axis::Weight,
+                value: 900.0,
+                min: 100.0,
+                max: 700.0
+            })
+        );
+
+        let variation = IconVariation::DEFAULT.with_grade(100.0);
+        assert_eq!(
+            variation.validate(RANGES),
+            Err(VariationError::UnsupportedAxis(Axis::Grade))
+        );
+
+        let variation = IconVariation::DEFAULT.with_fill(f32::NAN);
+        assert!(variation.validate(RANGES).is_err());
+    }
+
+    #[test]
+    fn test_instance_family_name() {
+        let family = "Material Symbols Sharp";
+        let name = |variation: IconVariation| variation.instance_family_name(family);
+        assert_eq!(name(IconVariation::DEFAULT), "Material Symbols Sharp 400");
+        assert_eq!(name(IconVariation::BOLD), "Material Symbols Sharp 700");
+        assert_eq!(
+            name(IconVariation::FILLED),
+            "Material Symbols Sharp Filled 400"
+        );
+        assert_eq!(
+            name(
+                IconVariation::FILLED_BOLD
+                    .with_grade(-25.0)
+                    .with_optical_size(48.0)
+            ),
+            "Material Symbols Sharp Filled 700 Grade -25 Opsz 48"
+        );
+        assert_eq!(
+            name(IconVariation::DEFAULT.with_fill(0.5).with_weight(350.0)),
+            "Material Symbols Sharp Fill 0.5 350"
+        );
+    }
+}